@@ -0,0 +1,56 @@
+//! Compares buffered and streaming decoding of a large record list.
+//!
+//! Run with `cargo bench --bench list_decode`.
+
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default, Deserialize)]
+#[allow(dead_code)]
+struct Record {
+    id: String,
+    title: String,
+}
+
+const ITEMS: usize = 50_000;
+const ROUNDS: u32 = 5;
+
+async fn time_rounds<F, Fut>(mut call: F) -> Duration
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = usize>,
+{
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        assert_eq!(call().await, ITEMS);
+    }
+    started.elapsed() / ROUNDS
+}
+
+#[tokio::main]
+async fn main() {
+    let items: Vec<_> = (0..ITEMS)
+        .map(|i| json!({ "id": format!("r{}", i), "title": "x".repeat(200) }))
+        .collect();
+    let body = json!({ "page": 1, "perPage": ITEMS, "totalItems": ITEMS, "items": items });
+    let body_len = body.to_string().len();
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(body);
+    });
+    let client = Client::new(server.base_url().as_str());
+    let list = client.records("posts").list().per_page(ITEMS as i32);
+
+    let buffered = time_rounds(|| async { list.call::<Record>().await.unwrap().items.len() }).await;
+    let streamed =
+        time_rounds(|| async { list.call_streaming::<Record>().await.unwrap().items.len() }).await;
+
+    println!("{} items, {} bytes per response", ITEMS, body_len);
+    println!("call:           {:?} per response", buffered);
+    println!("call_streaming: {:?} per response", streamed);
+}
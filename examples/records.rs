@@ -10,7 +10,7 @@ pub struct Product {
     pub created: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewProduct {
     pub name: String,
     pub count: i32,
@@ -1,36 +1,31 @@
-use crate::client::Auth;
-use crate::client::Client;
+use crate::client::{Auth, AuthResult, Client, SUPERUSERS_COLLECTION};
 use crate::httpc::Httpc;
 use anyhow::{anyhow, Result};
-use serde::Deserialize;
 use serde_json::json;
 
+/// Admin login for PocketBase servers up to 0.22, which authenticate admins
+/// through `/api/admins`. Newer servers use [`Client::auth_as_admin`].
 pub struct Admin<'a> {
     pub base_url: &'a str,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct AuthSuccessResponse {
-    token: String,
-}
-
 impl<'a> Admin<'a> {
+    /// Log in as an admin. The returned client refreshes its token through
+    /// `/api/admins/auth-refresh`.
     pub async fn auth_with_password(&self, identifier: &str, secret: &str) -> Result<Client<Auth>> {
-        let url = format!("{}/api/admins/auth-with-password", self.base_url);
+        let client = Client::new(self.base_url);
+        let url = client.endpoint("admins/auth-with-password");
         let credentials = json!({
             "identity": identifier,
             "password": secret,
         });
-        let client = Client::new(self.base_url);
         match Httpc::post(&client, &url, credentials.to_string()).await {
             Ok(response) => {
-                let raw_response = response.json::<AuthSuccessResponse>().await;
+                let raw_response = response.json::<AuthResult>().await;
                 match raw_response {
-                    Ok(AuthSuccessResponse { token }) => Ok(Client {
-                        base_url: self.base_url.to_string(),
-                        state: Auth,
-                        auth_token: Some(token),
-                    }),
+                    Ok(auth) => Ok(client
+                        .authenticated(SUPERUSERS_COLLECTION, auth)
+                        .refreshing_through("admins/auth-refresh")),
                     Err(e) => Err(anyhow!("{}", e)),
                 }
             }
@@ -0,0 +1,87 @@
+use crate::client::Client;
+use crate::httpc::Httpc;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Create, update and delete operations sent to `/api/batch` and applied by
+/// the server in a single transaction.
+#[derive(Clone, Debug)]
+pub struct BatchRequestBuilder<'a, A> {
+    pub client: &'a Client<A>,
+    pub requests: Vec<Value>,
+}
+
+/// The outcome of one queued operation, in the order it was queued.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchResult {
+    pub status: u16,
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl<'a, A: Clone> BatchRequestBuilder<'a, A> {
+    /// Queue creating `record` in `collection`; fails if `record` does not
+    /// serialize.
+    pub fn create<T: Serialize>(&self, collection: &str, record: T) -> Result<Self> {
+        let body = serde_json::to_value(record)?;
+        Ok(self.push(json!({
+            "method": "POST",
+            "url": format!("/api/collections/{}/records", collection),
+            "body": body,
+        })))
+    }
+
+    /// Queue updating record `id` in `collection` with `record`.
+    pub fn update<T: Serialize>(&self, collection: &str, id: &str, record: T) -> Result<Self> {
+        let body = serde_json::to_value(record)?;
+        Ok(self.push(json!({
+            "method": "PATCH",
+            "url": format!("/api/collections/{}/records/{}", collection, id),
+            "body": body,
+        })))
+    }
+
+    /// Queue deleting record `id` from `collection`.
+    pub fn delete(&self, collection: &str, id: &str) -> Self {
+        self.push(json!({
+            "method": "DELETE",
+            "url": format!("/api/collections/{}/records/{}", collection, id),
+        }))
+    }
+
+    fn push(&self, request: Value) -> Self {
+        let mut requests = self.requests.clone();
+        requests.push(request);
+        Self {
+            requests,
+            ..self.clone()
+        }
+    }
+}
+
+impl<'a, A> BatchRequestBuilder<'a, A> {
+    /// Submit every queued operation at once. Either all of them are applied
+    /// or, when one fails, none are and an error is returned.
+    pub async fn call(&self) -> Result<Vec<BatchResult>> {
+        if self.requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = self.client.endpoint("batch");
+        let payload = json!({ "requests": self.requests });
+        let response = Httpc::post(self.client, &url, payload.to_string()).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Batch request failed: HTTP {}: {}",
+                status.as_u16(),
+                body
+            ));
+        }
+
+        Ok(response.json::<Vec<BatchResult>>().await?)
+    }
+}
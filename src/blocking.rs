@@ -0,0 +1,461 @@
+use crate::client::{
+    Auth, AuthError, Client, HealthCheckResponse, NoAuth, PublicWrite, WriteAccess,
+};
+use crate::collections::{
+    Collection, CollectionList, CollectionListRequestBuilder, CollectionViewRequestBuilder,
+    CollectionsManager,
+};
+use crate::error::{RecordListError, RecordViewError, RecordWriteError};
+use crate::logs::{
+    LogList, LogListItem, LogListRequestBuilder, LogViewRequestBuilder, LogsManager,
+};
+use crate::records::{
+    CreateResponse, RecordCreateRequestBuilder, RecordDeleteAllRequestBuilder,
+    RecordDestroyRequestBuilder, RecordList, RecordUpdateRequestBuilder, RecordViewRequestBuilder,
+    RecordsListRequestBuilder, RecordsManager,
+};
+use crate::retry::RetryPolicy;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+/// A client whose calls block the current thread until the response is read,
+/// for CLI tools and scripts that don't run an async runtime.
+///
+/// It wraps the async [`Client`] and drives it on an internal single-threaded
+/// runtime shared by its clones. Blocking inside an async runtime panics, so
+/// async code should use [`Client`] directly.
+#[derive(Debug, Clone)]
+pub struct BlockingClient<A> {
+    client: Client<A>,
+    runtime: Arc<Runtime>,
+}
+
+/// A request builder from a [`BlockingClient`]; `call` blocks until the
+/// response is read.
+#[derive(Debug, Clone)]
+pub struct BlockingRequest<'a, B> {
+    runtime: &'a Runtime,
+    builder: B,
+}
+
+pub struct BlockingRecordsManager<'a, A> {
+    runtime: &'a Runtime,
+    records: RecordsManager<'a, A>,
+}
+
+pub struct BlockingCollectionsManager<'a, A> {
+    runtime: &'a Runtime,
+    collections: CollectionsManager<'a, A>,
+}
+
+pub struct BlockingLogsManager<'a, A> {
+    runtime: &'a Runtime,
+    logs: LogsManager<'a, A>,
+}
+
+impl BlockingClient<NoAuth> {
+    /// Construct a new “no‐auth” blocking client.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::from_client(Client::new(base_url))
+    }
+
+    /// Authenticate with identity/password, see [`Client::auth_with_password`].
+    pub fn auth_with_password(
+        &self,
+        collection: &str,
+        identifier: &str,
+        secret: &str,
+    ) -> Result<BlockingClient<Auth>, AuthError> {
+        let client = self.block_on(
+            self.client
+                .auth_with_password(collection, identifier, secret),
+        )?;
+        Ok(self.with_client(client))
+    }
+
+    /// Authenticate as a superuser, see [`Client::auth_as_admin`].
+    pub fn auth_as_admin(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<BlockingClient<Auth>, AuthError> {
+        let client = self.block_on(self.client.auth_as_admin(email, password))?;
+        Ok(self.with_client(client))
+    }
+
+    /// Opt in to record writes without a token, see [`Client::with_public_writes`].
+    pub fn with_public_writes(&self) -> BlockingClient<PublicWrite> {
+        self.with_client(self.client.with_public_writes())
+    }
+}
+
+impl<A> BlockingClient<A> {
+    /// Wrap an already configured async `client`.
+    pub fn from_client(client: Client<A>) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(BlockingClient {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The wrapped async client.
+    pub fn client(&self) -> &Client<A> {
+        &self.client
+    }
+
+    /// Run `future` to completion on the internal runtime, for async
+    /// operations without a blocking counterpart.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn health_check(&self) -> Result<HealthCheckResponse> {
+        self.block_on(self.client.health_check())
+    }
+
+    pub fn records<'a>(&'a self, record_name: &'a str) -> BlockingRecordsManager<'a, A> {
+        BlockingRecordsManager {
+            runtime: &self.runtime,
+            records: self.client.records(record_name),
+        }
+    }
+
+    pub fn collections(&self) -> BlockingCollectionsManager<'_, A> {
+        BlockingCollectionsManager {
+            runtime: &self.runtime,
+            collections: self.client.collections(),
+        }
+    }
+
+    pub fn logs(&self) -> BlockingLogsManager<'_, A> {
+        BlockingLogsManager {
+            runtime: &self.runtime,
+            logs: self.client.logs(),
+        }
+    }
+
+    fn with_client<B>(&self, client: Client<B>) -> BlockingClient<B> {
+        BlockingClient {
+            client,
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+}
+
+impl<'a, B> BlockingRequest<'a, B> {
+    fn new(runtime: &'a Runtime, builder: B) -> Self {
+        BlockingRequest { runtime, builder }
+    }
+
+    fn map(&self, f: impl FnOnce(&B) -> B) -> Self {
+        BlockingRequest::new(self.runtime, f(&self.builder))
+    }
+
+    /// The wrapped async builder.
+    pub fn builder(&self) -> &B {
+        &self.builder
+    }
+}
+
+impl<'a, A: Clone> BlockingRecordsManager<'a, A> {
+    pub fn view(
+        &self,
+        identifier: &'a str,
+    ) -> BlockingRequest<'a, RecordViewRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.records.view(identifier))
+    }
+
+    pub fn list(&self) -> BlockingRequest<'a, RecordsListRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.records.list())
+    }
+
+    /// See [`RecordsManager::first`].
+    pub fn first<T: DeserializeOwned>(&self, filter: impl Into<String>) -> Result<Option<T>> {
+        self.runtime.block_on(self.records.first(filter))
+    }
+
+    /// See [`RecordsManager::find_by_email`].
+    pub fn find_by_email<T: DeserializeOwned>(&self, email: &str) -> Result<Option<T>> {
+        self.runtime.block_on(self.records.find_by_email(email))
+    }
+
+    pub fn get_all<T: Default + DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.runtime.block_on(self.records.get_all())
+    }
+
+    /// See [`RecordsManager::count`].
+    pub fn count(&self, filter: Option<&str>) -> Result<i32> {
+        self.runtime.block_on(self.records.count(filter))
+    }
+
+    /// See [`RecordsManager::list_ids`].
+    pub fn list_ids(&self, filter: Option<&str>) -> Result<Vec<String>> {
+        self.runtime.block_on(self.records.list_ids(filter))
+    }
+}
+
+impl<'a, A: Clone + WriteAccess> BlockingRecordsManager<'a, A> {
+    pub fn create<T: Serialize + Clone>(
+        &self,
+        record: T,
+    ) -> BlockingRequest<'a, RecordCreateRequestBuilder<'a, A, T>> {
+        BlockingRequest::new(self.runtime, self.records.create(record))
+    }
+
+    pub fn update<T: Serialize + Clone>(
+        &self,
+        identifier: &'a str,
+        record: T,
+    ) -> BlockingRequest<'a, RecordUpdateRequestBuilder<'a, A, T>> {
+        BlockingRequest::new(self.runtime, self.records.update(identifier, record))
+    }
+
+    pub fn destroy(
+        &self,
+        identifier: &'a str,
+    ) -> BlockingRequest<'a, RecordDestroyRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.records.destroy(identifier))
+    }
+
+    /// See [`RecordsManager::delete_all`].
+    pub fn delete_all(
+        &self,
+        filter: &'a str,
+    ) -> BlockingRequest<'a, RecordDeleteAllRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.records.delete_all(filter))
+    }
+}
+
+impl<'a, A: Clone> BlockingRequest<'a, RecordsListRequestBuilder<'a, A>> {
+    pub fn call<T: Default + DeserializeOwned>(&self) -> Result<RecordList<T>, RecordListError> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    /// Fetch every page, see [`RecordsListRequestBuilder::get_all`].
+    pub fn get_all<T: Default + DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.runtime.block_on(self.builder.get_all())
+    }
+
+    pub fn filter(&self, filter_opts: impl Into<String>) -> Self {
+        self.map(|builder| builder.filter(filter_opts))
+    }
+
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
+        self.map(|builder| builder.sort(sort_opts))
+    }
+
+    pub fn expand(&self, expand_opts: &str) -> Self {
+        self.map(|builder| builder.expand(expand_opts))
+    }
+
+    pub fn fields(&self, fields_opts: &str) -> Self {
+        self.map(|builder| builder.fields(fields_opts))
+    }
+
+    pub fn page(&self, page: i32) -> Self {
+        self.map(|builder| builder.page(page))
+    }
+
+    pub fn per_page(&self, per_page: i32) -> Self {
+        self.map(|builder| builder.per_page(per_page))
+    }
+
+    pub fn skip_total(&self, skip_total: bool) -> Self {
+        self.map(|builder| builder.skip_total(skip_total))
+    }
+
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry(policy))
+    }
+
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        self.map(|builder| builder.query_param(key, value))
+    }
+
+    pub fn request_key(&self, key: &str) -> Self {
+        self.map(|builder| builder.request_key(key))
+    }
+}
+
+impl<'a, A> BlockingRequest<'a, RecordViewRequestBuilder<'a, A>> {
+    pub fn call<T: Default + DeserializeOwned>(&self) -> Result<T, RecordViewError> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn expand(&self, expand_opts: &str) -> Self {
+        self.map(|builder| builder.expand(expand_opts))
+    }
+
+    pub fn fields(&self, fields_opts: &str) -> Self {
+        self.map(|builder| builder.fields(fields_opts))
+    }
+
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry(policy))
+    }
+
+    pub fn request_key(&self, key: &str) -> Self {
+        self.map(|builder| builder.request_key(key))
+    }
+}
+
+impl<'a, A: Clone, T: Serialize + Clone> BlockingRequest<'a, RecordCreateRequestBuilder<'a, A, T>> {
+    pub fn call(&self) -> Result<CreateResponse, RecordWriteError> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn file_from_path(&self, field: &str, path: &Path) -> Self {
+        self.map(|builder| builder.file_from_path(field, path))
+    }
+
+    pub fn file_from_bytes(&self, field: &str, filename: &str, bytes: Vec<u8>) -> Self {
+        self.map(|builder| builder.file_from_bytes(field, filename, bytes))
+    }
+
+    pub fn validate_with(&self, collection: &Collection) -> Self {
+        self.map(|builder| builder.validate_with(collection))
+    }
+
+    pub fn omit_none(&self) -> Self {
+        self.map(|builder| builder.omit_none())
+    }
+
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry(policy))
+    }
+}
+
+impl<'a, A: Clone, T: Serialize + Clone + DeserializeOwned>
+    BlockingRequest<'a, RecordUpdateRequestBuilder<'a, A, T>>
+{
+    pub fn call(&self) -> Result<T, RecordWriteError> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn validate_with(&self, collection: &Collection) -> Self {
+        self.map(|builder| builder.validate_with(collection))
+    }
+
+    pub fn omit_none(&self) -> Self {
+        self.map(|builder| builder.omit_none())
+    }
+
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry(policy))
+    }
+}
+
+impl<'a, A: Clone> BlockingRequest<'a, RecordDestroyRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<()> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        self.map(|builder| builder.retry(policy))
+    }
+}
+
+impl<'a, A: Clone + WriteAccess> BlockingRequest<'a, RecordDeleteAllRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<usize> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn concurrency(&self, concurrency: usize) -> Self {
+        self.map(|builder| builder.concurrency(concurrency))
+    }
+}
+
+impl<'a, A: Clone> BlockingCollectionsManager<'a, A> {
+    pub fn view(&self, name: &'a str) -> BlockingRequest<'a, CollectionViewRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.collections.view(name))
+    }
+
+    pub fn list(&self) -> BlockingRequest<'a, CollectionListRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.collections.list())
+    }
+
+    /// See [`CollectionsManager::get_all`].
+    pub fn get_all(&self) -> Result<Vec<Collection>> {
+        self.runtime.block_on(self.collections.get_all())
+    }
+}
+
+impl<'a, A: Clone> BlockingRequest<'a, CollectionViewRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<Collection> {
+        self.runtime.block_on(self.builder.call())
+    }
+}
+
+impl<'a, A: Clone> BlockingRequest<'a, CollectionListRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<CollectionList> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn filter(&self, filter_opts: impl Into<String>) -> Self {
+        self.map(|builder| builder.filter(filter_opts))
+    }
+
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
+        self.map(|builder| builder.sort(sort_opts))
+    }
+
+    pub fn page(&self, page_count: i32) -> Self {
+        self.map(|builder| builder.page(page_count))
+    }
+
+    pub fn per_page(&self, per_page_count: i32) -> Self {
+        self.map(|builder| builder.per_page(per_page_count))
+    }
+
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        self.map(|builder| builder.query_param(key, value))
+    }
+}
+
+impl<'a, A: Clone> BlockingLogsManager<'a, A> {
+    pub fn view(&self, id: &'a str) -> BlockingRequest<'a, LogViewRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.logs.view(id))
+    }
+
+    pub fn list(&self) -> BlockingRequest<'a, LogListRequestBuilder<'a, A>> {
+        BlockingRequest::new(self.runtime, self.logs.list())
+    }
+}
+
+impl<'a, A> BlockingRequest<'a, LogViewRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<LogListItem> {
+        self.runtime.block_on(self.builder.call())
+    }
+}
+
+impl<'a, A: Clone> BlockingRequest<'a, LogListRequestBuilder<'a, A>> {
+    pub fn call(&self) -> Result<LogList> {
+        self.runtime.block_on(self.builder.call())
+    }
+
+    pub fn filter(&self, filter_opts: &'a str) -> Self {
+        self.map(|builder| builder.filter(filter_opts))
+    }
+
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
+        self.map(|builder| builder.sort(sort_opts))
+    }
+
+    pub fn page(&self, page_count: i32) -> Self {
+        self.map(|builder| builder.page(page_count))
+    }
+
+    pub fn per_page(&self, per_page_count: i32) -> Self {
+        self.map(|builder| builder.per_page(per_page_count))
+    }
+
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        self.map(|builder| builder.query_param(key, value))
+    }
+}
@@ -1,36 +1,288 @@
+use crate::batch::BatchRequestBuilder;
+use crate::error::{RequestAbortedError, TimeoutError};
 use crate::httpc::Httpc;
+use crate::observer::{Observer, RequestObserver};
+use crate::retry::RetryPolicy;
+use crate::rts::RealtimeManager;
+use crate::transport::{SharedTransport, Transport};
 use crate::{collections::CollectionsManager, logs::LogsManager, records::RecordsManager};
-use anyhow::{anyhow, Result};
-use reqwest::StatusCode;
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use futures::future::{abortable, AbortHandle};
+use reqwest::header::HeaderMap;
+use reqwest::{Client as ReqwestClient, Proxy, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::env;
 use std::fmt;
+use std::future::Future;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 use thiserror::Error;
 
-#[derive(Debug, Deserialize)]
-struct AuthSuccessResponse {
-    token: String,
+/// The body of a successful auth request: the token and the record it was
+/// issued for.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthResult<T = Value> {
+    pub token: String,
+    /// The authenticated record. Servers up to 0.22 send admins as `admin`;
+    /// responses without either leave it at its default.
+    #[serde(default, alias = "admin")]
+    pub record: T,
+}
+
+/// The auth methods a collection allows, from [`Client::list_auth_methods`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthMethodsList {
+    #[serde(default)]
+    pub username_password: bool,
+    #[serde(default)]
+    pub email_password: bool,
+    #[serde(default)]
+    pub auth_providers: Vec<AuthProviderInfo>,
+}
+
+/// An OAuth2 provider along with the PKCE values to start its flow with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthProviderInfo {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    pub state: String,
+    pub auth_url: String,
+    pub code_verifier: String,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct NoAuth;
 
+/// A client without a token that may still write to collections whose API
+/// rules allow anonymous writes; see [`Client::with_public_writes`].
+#[derive(Debug, Clone)]
+pub struct PublicWrite;
+
+/// Client states that can use the mutating record builders (create, update,
+/// delete). Writing from a [`NoAuth`] client is a compile error rather than
+/// a 403 at runtime.
+pub trait WriteAccess {}
+
+impl WriteAccess for Auth {}
+
+impl WriteAccess for PublicWrite {}
+
 #[derive(Debug, Clone)]
-pub struct Auth;
+pub struct Auth {
+    /// The auth collection the token was issued for, e.g. `users`.
+    pub collection: String,
+    /// The authenticated record as returned by the server, or `Null` when
+    /// only a token is known.
+    pub record: Value,
+    /// Authenticated as a superuser, as required for managing collections.
+    pub superuser: bool,
+}
+
+/// The claims of a PocketBase auth token, read by [`Client::token_claims`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenClaims {
+    /// The authenticated record's id.
+    pub id: String,
+    #[serde(default)]
+    pub collection_id: String,
+    /// The token kind, e.g. `auth`.
+    #[serde(default)]
+    pub r#type: String,
+    /// When the token expires.
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub exp: DateTime<Utc>,
+}
+
+/// How the token is written into the `Authorization` header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// The bare token, as PocketBase expects by default.
+    #[default]
+    Raw,
+    /// `Bearer <token>`, for gateways and proxies that require a scheme.
+    Bearer,
+}
+
+impl AuthScheme {
+    /// The header value for `token`. Tokens that already carry a `Bearer `
+    /// prefix are sent as given.
+    pub fn header_value(&self, token: &str) -> String {
+        match self {
+            AuthScheme::Bearer if !token.starts_with("Bearer ") => format!("Bearer {}", token),
+            _ => token.to_string(),
+        }
+    }
+}
+
+/// Automatic token refresh state, shared by an authenticated client and its
+/// clones so a token refreshed by one request is used by all later ones.
+#[derive(Debug)]
+pub(crate) struct Session {
+    /// The endpoint the token is refreshed through, relative to the API
+    /// prefix.
+    pub(crate) refresh_path: String,
+    /// The token from the latest automatic refresh, if any.
+    pub(crate) refreshed_token: RwLock<Option<String>>,
+}
+
+/// PocketBase's default upper bound on `perPage`.
+pub const DEFAULT_MAX_PER_PAGE: i32 = 500;
+
+/// The path PocketBase serves its API under.
+pub const DEFAULT_API_PREFIX: &str = "/api";
+
+/// The auth collection holding superusers (admins).
+pub const SUPERUSERS_COLLECTION: &str = "_superusers";
 
 #[derive(Debug, Clone)]
 pub struct Client<State> {
     pub base_url: String,
-    pub auth_token: Option<String>,
+    /// Path the API is mounted under, joined between `base_url` and each
+    /// endpoint; [`DEFAULT_API_PREFIX`] unless changed.
+    pub api_prefix: String,
+    /// The token from login; read the one in use with [`Client::auth_token`].
+    pub(crate) auth_token: Option<String>,
     pub state: State,
+    /// Shared HTTP client; cloning is cheap and reuses its connection pool.
+    pub http_client: ReqwestClient,
+    /// Default retry policy for idempotent requests; `None` never retries.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Per-request timeout; `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    /// Format of the `Authorization` header; the bare token by default.
+    pub auth_scheme: AuthScheme,
+    /// Headers sent with every request, e.g. `X-Tenant`. Headers a request
+    /// sets itself, such as `Content-Type` or `Authorization`, take precedence.
+    pub default_headers: HeaderMap,
+    /// Largest `perPage` the server accepts; `get_all` and `stream` page by
+    /// it and clamp larger page sizes to it.
+    pub max_per_page: i32,
+    /// Largest record list or view response body that is read; `None` reads
+    /// bodies of any size.
+    pub max_body_size: Option<usize>,
+    /// Settings `http_client` was built with, kept so each builder option
+    /// that rebuilds it preserves the others.
+    pub(crate) connection: ConnectionSettings,
+    /// In-flight keyed requests, shared by clones of this client.
+    pub(crate) request_keys: Arc<RequestKeys>,
+    /// Called after every request; see [`Client::with_request_observer`].
+    pub(crate) observer: Option<Observer>,
+    /// Sends requests instead of `http_client`; see [`Client::with_transport`].
+    pub(crate) transport: Option<SharedTransport>,
+    /// Set for authenticated clients; requests answered with 401 refresh the
+    /// token once through it and are retried.
+    pub(crate) session: Option<Arc<Session>>,
+}
+
+/// Options applied when building the HTTP client itself rather than per
+/// request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionSettings {
+    proxy: Option<Proxy>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    accept_invalid_certs: bool,
+}
+
+impl ConnectionSettings {
+    fn build(&self) -> ReqwestClient {
+        let mut builder = ReqwestClient::builder();
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+        }
+        builder.build().expect("HTTP client cannot be initialized")
+    }
+}
+
+/// In-flight requests started with a request key, so a newer request with
+/// the same key can abort the older one.
+#[derive(Debug, Default)]
+pub(crate) struct RequestKeys {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<String, (u64, AbortHandle)>>,
+}
+
+/// Forgets a keyed request once it finished or was dropped, unless a newer
+/// request has taken over its key.
+struct PendingKey<'a> {
+    keys: &'a RequestKeys,
+    key: &'a str,
+    id: u64,
+}
+
+impl Drop for PendingKey<'_> {
+    fn drop(&mut self) {
+        let mut pending = self.keys.pending.lock().unwrap();
+        if pending.get(self.key).is_some_and(|(id, _)| *id == self.id) {
+            pending.remove(self.key);
+        }
+    }
+}
+
+/// A client built by [`Client::from_env`], authenticated only when the
+/// environment carries a token or credentials.
+#[derive(Debug, Clone)]
+pub enum EnvClient {
+    NoAuth(Client<NoAuth>),
+    Auth(Client<Auth>),
+}
+
+/// The status and headers of a response, returned alongside the decoded
+/// body by the `call_with_meta` builder methods.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    pub(crate) fn of(response: &Response) -> Self {
+        ResponseMeta {
+            status: response.status(),
+            headers: response.headers().clone(),
+        }
+    }
+
+    /// The value of header `name`, e.g. `x-ratelimit-remaining`, if present
+    /// and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct HealthCheckResponse {
     pub code: i32,
     pub message: String,
+    /// Extra server details such as `canBackup`; only sent by newer
+    /// PocketBase versions.
+    #[serde(default)]
+    pub data: Option<HashMap<String, Value>>,
+}
+
+impl HealthCheckResponse {
+    /// Whether the server can create a backup right now, or `None` when it
+    /// doesn't report it.
+    pub fn can_backup(&self) -> Option<bool> {
+        self.data.as_ref()?.get("canBackup")?.as_bool()
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -48,14 +300,27 @@ impl fmt::Display for ValidationError {
 #[derive(Debug, Clone, PartialEq, Deserialize, Error, Serialize)]
 #[error("{message} ({status})")]
 pub struct ErrorResponse {
+    #[serde(default)]
     pub data: HashMap<String, ValidationError>,
     pub message: String,
+    #[serde(alias = "code")]
     pub status: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AuthError {
+    /// The server rejected the request with a PocketBase error body, e.g.
+    /// wrong credentials.
     Validation(ErrorResponse),
+    /// The server could not be reached or the connection failed, e.g. a DNS
+    /// failure, refused connection or timeout.
+    Network(String),
+    /// The server answered with an unexpected status and no PocketBase error
+    /// body, e.g. a 500 or a proxy's 502.
+    Server {
+        status: u16,
+        body: String,
+    },
     Other(String),
 }
 
@@ -69,13 +334,23 @@ impl From<ErrorResponse> for AuthError {
 
 impl From<anyhow::Error> for AuthError {
     fn from(err: anyhow::Error) -> Self {
-        AuthError::Other(err.to_string())
+        if err.is::<TimeoutError>() {
+            return AuthError::Network(err.to_string());
+        }
+        match err.downcast::<reqwest::Error>() {
+            Ok(err) => err.into(),
+            Err(err) => AuthError::Other(err.to_string()),
+        }
     }
 }
 
 impl From<reqwest::Error> for AuthError {
     fn from(err: reqwest::Error) -> Self {
-        AuthError::Other(err.to_string())
+        if err.is_decode() {
+            AuthError::Other(err.to_string())
+        } else {
+            AuthError::Network(err.to_string())
+        }
     }
 }
 
@@ -83,6 +358,8 @@ impl From<reqwest::Error> for AuthError {
 #[serde(tag = "variant", content = "payload")]
 enum AuthErrorRepr {
     Validation(ErrorResponse),
+    Network(String),
+    Server { status: u16, body: String },
     Other(String),
 }
 
@@ -90,16 +367,17 @@ impl fmt::Display for AuthError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let repr = match self {
             AuthError::Validation(err) => AuthErrorRepr::Validation(err.clone()),
+            AuthError::Network(msg) => AuthErrorRepr::Network(msg.clone()),
+            AuthError::Server { status, body } => AuthErrorRepr::Server {
+                status: *status,
+                body: body.clone(),
+            },
             AuthError::Other(msg) => AuthErrorRepr::Other(msg.clone()),
         };
 
         match serde_json::to_string(&repr) {
             Ok(json_str) => write!(f, "{}", json_str),
-            Err(_) => write!(
-                f,
-                r#"{{"variant":"Other","payload":"{}"}}"#,
-                self.to_string()
-            ),
+            Err(_) => write!(f, r#"{{"variant":"Other","payload":"{:?}"}}"#, self),
         }
     }
 }
@@ -110,6 +388,8 @@ impl FromStr for AuthError {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match serde_json::from_str::<AuthErrorRepr>(s) {
             Ok(AuthErrorRepr::Validation(err)) => Ok(AuthError::Validation(err)),
+            Ok(AuthErrorRepr::Network(msg)) => Ok(AuthError::Network(msg)),
+            Ok(AuthErrorRepr::Server { status, body }) => Ok(AuthError::Server { status, body }),
             Ok(AuthErrorRepr::Other(msg)) => Ok(AuthError::Other(msg)),
             Err(_) => Ok(AuthError::Other(s.to_string())),
         }
@@ -117,12 +397,161 @@ impl FromStr for AuthError {
 }
 
 impl<A> Client<A> {
-    pub fn collections(&self) -> CollectionsManager<A> {
+    /// Retry idempotent requests (GET/DELETE) according to `policy`.
+    pub fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        Client {
+            retry_policy: Some(policy),
+            ..self
+        }
+    }
+
+    /// Fail requests that take longer than `timeout` with a
+    /// [`TimeoutError`](crate::error::TimeoutError). Realtime event streams
+    /// are long-lived and not affected.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Client {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// **Dangerous, for local development only:** when `accept` is true,
+    /// skip TLS certificate validation so a server with a self-signed
+    /// certificate can be reached. Any certificate is trusted, including one
+    /// presented by an attacker.
+    ///
+    /// This replaces the HTTP client, including one passed to
+    /// [`Client::with_http_client`].
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn danger_accept_invalid_certs(self, accept: bool) -> Self {
+        let mut connection = self.connection.clone();
+        connection.accept_invalid_certs = accept;
+        Client {
+            http_client: connection.build(),
+            connection,
+            ..self
+        }
+    }
+
+    /// Send requests through `proxy`, e.g.
+    /// `reqwest::Proxy::all("http://proxy.corp:3128")`. Timeouts, headers and
+    /// the other client options still apply.
+    ///
+    /// This replaces the HTTP client, including one passed to
+    /// [`Client::with_http_client`].
+    pub fn with_proxy(self, proxy: Proxy) -> Self {
+        let mut connection = self.connection.clone();
+        connection.proxy = Some(proxy);
+        Client {
+            http_client: connection.build(),
+            connection,
+            ..self
+        }
+    }
+
+    /// Send the token in the `Authorization` header using `scheme`.
+    pub fn with_auth_scheme(self, scheme: AuthScheme) -> Self {
+        Client {
+            auth_scheme: scheme,
+            ..self
+        }
+    }
+
+    /// Send `headers` with every request, e.g. a tenant id or
+    /// `Accept-Language`.
+    pub fn with_default_headers(self, headers: HeaderMap) -> Self {
+        Client {
+            default_headers: headers,
+            ..self
+        }
+    }
+
+    /// Set the largest `perPage` the server accepts, for servers configured
+    /// with a limit other than [`DEFAULT_MAX_PER_PAGE`]. Values below 1 are
+    /// treated as 1.
+    pub fn with_max_per_page(self, max_per_page: i32) -> Self {
+        Client {
+            max_per_page: max_per_page.max(1),
+            ..self
+        }
+    }
+
+    /// Fail record list and view calls whose response body is larger than
+    /// `bytes` with a [`BodyTooLargeError`](crate::error::BodyTooLargeError)
+    /// instead of reading it into memory.
+    pub fn with_max_body_size(self, bytes: usize) -> Self {
+        Client {
+            max_body_size: Some(bytes),
+            ..self
+        }
+    }
+
+    /// Call `observer` with the method, URL, status and duration of every
+    /// request, e.g. for logging or metrics. Realtime event streams are
+    /// long-lived and not reported.
+    pub fn with_request_observer(self, observer: impl RequestObserver + 'static) -> Self {
+        Client {
+            observer: Some(Observer(Arc::new(observer))),
+            ..self
+        }
+    }
+
+    /// Send requests through `transport` instead of the HTTP client, e.g. a
+    /// `MockTransport` from the `test-util` feature in tests.
+    pub fn with_transport(self, transport: impl Transport + 'static) -> Self {
+        Client {
+            transport: Some(SharedTransport(Arc::new(transport))),
+            ..self
+        }
+    }
+
+    /// [`max_per_page`](Self::max_per_page), at least 1 even if the field
+    /// was set directly.
+    pub(crate) fn per_page_limit(&self) -> i32 {
+        self.max_per_page.max(1)
+    }
+
+    /// The transport requests are sent through.
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        match &self.transport {
+            Some(transport) => &*transport.0,
+            None => &self.http_client,
+        }
+    }
+
+    /// Serve the API under `prefix` instead of [`DEFAULT_API_PREFIX`], e.g.
+    /// for a server with a custom route prefix. An empty prefix puts
+    /// endpoints directly under `base_url`.
+    pub fn with_api_prefix(self, prefix: &str) -> Self {
+        Client {
+            api_prefix: prefix.to_string(),
+            ..self
+        }
+    }
+
+    /// The full URL of the API endpoint at `path`, e.g. `collections/posts`.
+    /// Slashes between `base_url`, the API prefix and `path` are normalized,
+    /// so a trailing slash on `base_url` doesn't produce `//`.
+    pub fn endpoint(&self, path: &str) -> String {
+        let mut url = self.base_url.trim_end_matches('/').to_string();
+        for segment in [
+            self.api_prefix.trim_matches('/'),
+            path.trim_start_matches('/'),
+        ] {
+            if !segment.is_empty() {
+                url.push('/');
+                url.push_str(segment);
+            }
+        }
+        url
+    }
+
+    pub fn collections(&self) -> CollectionsManager<'_, A> {
         CollectionsManager { client: self }
     }
 
     pub async fn health_check(&self) -> Result<HealthCheckResponse> {
-        let url = format!("{}/api/health", self.base_url);
+        let url = self.endpoint("health");
         let response = Httpc::get(self, &url, None)
             .await
             .map_err(|e| anyhow!("Network error: {}", e))?;
@@ -131,11 +560,112 @@ impl<A> Client<A> {
         Ok(hc)
     }
 
-    pub fn logs(&self) -> LogsManager<A> {
+    /// Open a pooled connection with a health check ahead of a burst of
+    /// requests, so the first of them doesn't pay for the connection setup.
+    pub async fn warm_up(&self) -> Result<()> {
+        self.health_check().await?;
+        Ok(())
+    }
+
+    /// List the password and OAuth2 auth methods enabled for `collection`.
+    pub async fn list_auth_methods(&self, collection: &str) -> Result<AuthMethodsList> {
+        let url = self.endpoint(&format!("collections/{}/auth-methods", collection));
+        let response = Httpc::get(self, &url, None)
+            .await
+            .map_err(|e| anyhow!("Network error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Listing auth methods failed: HTTP {}",
+                response.status().as_u16()
+            ));
+        }
+
+        Ok(response.json::<AuthMethodsList>().await?)
+    }
+
+    /// Email a password reset link to the `collection` record with `email`.
+    pub async fn request_password_reset(
+        &self,
+        collection: &str,
+        email: &str,
+    ) -> Result<(), AuthError> {
+        let url = self.endpoint(&format!(
+            "collections/{}/request-password-reset",
+            collection
+        ));
+        let payload = json!({ "email": email });
+
+        let response = Httpc::post(self, &url, payload.to_string()).await?;
+        self.read_empty_response(response).await
+    }
+
+    /// Set a new password using the `token` from a password reset email.
+    pub async fn confirm_password_reset(
+        &self,
+        collection: &str,
+        token: &str,
+        new_password: &str,
+        new_password_confirm: &str,
+    ) -> Result<(), AuthError> {
+        let url = self.endpoint(&format!(
+            "collections/{}/confirm-password-reset",
+            collection
+        ));
+        let payload = json!({
+            "token": token,
+            "password": new_password,
+            "passwordConfirm": new_password_confirm
+        });
+
+        let response = Httpc::post(self, &url, payload.to_string()).await?;
+        self.read_empty_response(response).await
+    }
+
+    /// Email a verification link to the `collection` record with `email`.
+    pub async fn request_verification(
+        &self,
+        collection: &str,
+        email: &str,
+    ) -> Result<(), AuthError> {
+        let url = self.endpoint(&format!("collections/{}/request-verification", collection));
+        let payload = json!({ "email": email });
+
+        let response = Httpc::post(self, &url, payload.to_string()).await?;
+        self.read_empty_response(response).await
+    }
+
+    /// Mark a record as verified using the `token` from a verification email.
+    pub async fn confirm_verification(
+        &self,
+        collection: &str,
+        token: &str,
+    ) -> Result<(), AuthError> {
+        let url = self.endpoint(&format!("collections/{}/confirm-verification", collection));
+        let payload = json!({ "token": token });
+
+        let response = Httpc::post(self, &url, payload.to_string()).await?;
+        self.read_empty_response(response).await
+    }
+
+    /// Start a batch of record operations applied in a single transaction.
+    pub fn batch(&self) -> BatchRequestBuilder<'_, A> {
+        BatchRequestBuilder {
+            client: self,
+            requests: Vec::new(),
+        }
+    }
+
+    pub fn logs(&self) -> LogsManager<'_, A> {
         LogsManager { client: self }
     }
 
-    pub fn records(&self, record_name: &'static str) -> RecordsManager<A> {
+    pub fn realtime(&self) -> RealtimeManager<'_, A> {
+        RealtimeManager { client: self }
+    }
+
+    /// Records of the `record_name` collection. The name only needs to live
+    /// as long as the returned manager, so it can be built at runtime.
+    pub fn records<'a>(&'a self, record_name: &'a str) -> RecordsManager<'a, A> {
         RecordsManager {
             client: self,
             name: record_name,
@@ -146,13 +676,92 @@ impl<A> Client<A> {
 impl Client<NoAuth> {
     /// Construct a new “no‐auth” client:
     pub fn new(base_url: &str) -> Self {
+        Self::with_http_client(base_url, ReqwestClient::new())
+    }
+
+    /// Construct a “no‐auth” client around a pre-configured `reqwest::Client`,
+    /// e.g. one with custom TLS, proxy or pool settings.
+    ///
+    /// A trailing slash on `base_url` is dropped.
+    pub fn with_http_client(base_url: &str, http_client: ReqwestClient) -> Self {
         Client {
-            base_url: base_url.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_prefix: DEFAULT_API_PREFIX.to_string(),
             auth_token: None,
             state: NoAuth,
+            http_client,
+            retry_policy: None,
+            timeout: None,
+            auth_scheme: AuthScheme::default(),
+            default_headers: HeaderMap::new(),
+            max_per_page: DEFAULT_MAX_PER_PAGE,
+            max_body_size: None,
+            connection: ConnectionSettings::default(),
+            request_keys: Arc::default(),
+            observer: None,
+            transport: None,
+            session: None,
+        }
+    }
+
+    /// Opt in to record writes without a token, for collections whose
+    /// create, update or delete rule is public (`""`). The server still
+    /// enforces its rules; this only lifts the compile-time check.
+    pub fn with_public_writes(&self) -> Client<PublicWrite> {
+        Client {
+            base_url: self.base_url.clone(),
+            api_prefix: self.api_prefix.clone(),
+            auth_token: None,
+            state: PublicWrite,
+            http_client: self.http_client.clone(),
+            retry_policy: self.retry_policy.clone(),
+            timeout: self.timeout,
+            auth_scheme: self.auth_scheme,
+            default_headers: self.default_headers.clone(),
+            max_per_page: self.max_per_page,
+            max_body_size: self.max_body_size,
+            connection: self.connection.clone(),
+            request_keys: Arc::clone(&self.request_keys),
+            observer: self.observer.clone(),
+            transport: self.transport.clone(),
+            session: None,
         }
     }
 
+    /// Construct a client from the environment.
+    ///
+    /// `POCKETBASE_URL` is required. If `POCKETBASE_TOKEN` is set the client is
+    /// authenticated with it directly; otherwise, if `POCKETBASE_IDENTITY` is set,
+    /// `POCKETBASE_PASSWORD` is required and the client authenticates against
+    /// `POCKETBASE_COLLECTION` (default `users`). With neither, a “no‐auth”
+    /// client is returned.
+    pub async fn from_env() -> Result<EnvClient> {
+        let base_url = required_env("POCKETBASE_URL")?;
+        let client = Client::new(&base_url);
+
+        let collection = env::var("POCKETBASE_COLLECTION").unwrap_or_else(|_| "users".to_string());
+
+        if let Ok(token) = env::var("POCKETBASE_TOKEN") {
+            return Ok(EnvClient::Auth(client.authenticated(
+                &collection,
+                AuthResult {
+                    token,
+                    record: Value::Null,
+                },
+            )));
+        }
+
+        if let Ok(identity) = env::var("POCKETBASE_IDENTITY") {
+            let password = required_env("POCKETBASE_PASSWORD")?;
+            let authenticated = client
+                .auth_with_password(&collection, &identity, &password)
+                .await?;
+            return Ok(EnvClient::Auth(authenticated));
+        }
+
+        Ok(EnvClient::NoAuth(client))
+    }
+
     /// Attempt to authenticate with identity/password. On success, return `Client<Auth>`.
     pub async fn auth_with_password(
         &self,
@@ -160,39 +769,310 @@ impl Client<NoAuth> {
         identifier: &str,
         secret: &str,
     ) -> Result<Client<Auth>, AuthError> {
-        let url = format!(
-            "{}/api/collections/{}/auth-with-password",
-            self.base_url, collection
-        );
+        let url = self.endpoint(&format!("collections/{}/auth-with-password", collection));
         let auth_payload = json!({
             "identity": identifier,
             "password": secret
         });
 
         let response = Httpc::post(self, &url, auth_payload.to_string()).await?;
+        self.read_auth_response(collection, response).await
+    }
+
+    /// Authenticate as a superuser, as required for admin operations such as
+    /// creating or deleting collections.
+    pub async fn auth_as_admin(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Client<Auth>, AuthError> {
+        self.auth_with_password(SUPERUSERS_COLLECTION, email, password)
+            .await
+    }
+
+    /// Complete an OAuth2 login with the `code` the provider redirected back
+    /// with and the `code_verifier` from [`Client::list_auth_methods`].
+    pub async fn auth_with_oauth2(
+        &self,
+        collection: &str,
+        provider: &str,
+        code: &str,
+        code_verifier: &str,
+        redirect_url: &str,
+    ) -> Result<Client<Auth>, AuthError> {
+        let url = self.endpoint(&format!("collections/{}/auth-with-oauth2", collection));
+        let auth_payload = json!({
+            "provider": provider,
+            "code": code,
+            "codeVerifier": code_verifier,
+            "redirectUrl": redirect_url
+        });
+
+        let response = Httpc::post(self, &url, auth_payload.to_string()).await?;
+        self.read_auth_response(collection, response).await
+    }
+}
+
+impl Client<Auth> {
+    /// Build a client authenticated with a `token` obtained earlier for
+    /// `collection`, e.g. one restored from a cookie or keychain.
+    ///
+    /// With `validate`, the token is first exchanged via
+    /// [`auth_refresh`](Self::auth_refresh), so an expired or revoked token
+    /// fails here and the returned client carries the authenticated record.
+    pub async fn from_token(
+        base_url: &str,
+        collection: &str,
+        token: &str,
+        validate: bool,
+    ) -> Result<Client<Auth>, AuthError> {
+        let client = Client::new(base_url).authenticated(
+            collection,
+            AuthResult {
+                token: token.to_string(),
+                record: Value::Null,
+            },
+        );
+        if validate {
+            return client.auth_refresh().await;
+        }
+        Ok(client)
+    }
 
+    /// The id of the authenticated record, when the server returned one.
+    pub fn record_id(&self) -> Option<&str> {
+        self.state.record.get("id").and_then(Value::as_str)
+    }
+
+    /// Read the claims of the current token without a network call, e.g. to
+    /// refresh it before it expires. The signature is not verified.
+    pub fn token_claims(&self) -> Result<TokenClaims> {
+        let token = self
+            .current_token()
+            .ok_or_else(|| anyhow!("client has no auth token"))?;
+        let payload = token
+            .split('.')
+            .nth(1)
+            .ok_or_else(|| anyhow!("auth token is not a JWT"))?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .context("auth token payload is not base64")?;
+        serde_json::from_slice(&payload).context("auth token payload is not valid claims")
+    }
+
+    /// Whether the current token has expired. Tokens whose claims can't be
+    /// read count as expired.
+    pub fn is_token_expired(&self) -> bool {
+        self.expires_in().is_none_or(|left| left.is_zero())
+    }
+
+    /// Time left until the current token expires; `None` once it has expired
+    /// or when its claims can't be read.
+    pub fn expires_in(&self) -> Option<Duration> {
+        let claims = self.token_claims().ok()?;
+        (claims.exp - Utc::now()).to_std().ok()
+    }
+
+    /// Decode the authenticated record into `T`.
+    pub fn auth_record<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(self.state.record.clone())?)
+    }
+
+    /// Exchange the current token for a fresh one via the collection's
+    /// `auth-refresh` endpoint, returning a client carrying the new token.
+    pub async fn auth_refresh(&self) -> Result<Client<Auth>, AuthError> {
+        let refresh_path = match &self.session {
+            Some(session) => session.refresh_path.clone(),
+            None => refresh_path(&self.state.collection),
+        };
+        let url = self.endpoint(&refresh_path);
+        let response = Httpc::post(self, &url, String::new()).await?;
+        let refreshed = self
+            .read_auth_response(&self.state.collection, response)
+            .await?;
+        Ok(refreshed.refreshing_through(&refresh_path))
+    }
+
+    /// This client with its token refreshed through `refresh_path`, e.g.
+    /// `admins/auth-refresh` for admins of servers up to 0.22.
+    pub(crate) fn refreshing_through(self, refresh_path: &str) -> Self {
+        Client {
+            session: Some(Arc::new(Session {
+                refresh_path: refresh_path.to_string(),
+                refreshed_token: RwLock::new(None),
+            })),
+            ..self
+        }
+    }
+
+    /// Confirm the authenticated record's own verification `token`, then
+    /// refresh the token so the returned client's record reports `verified`.
+    pub async fn confirm_verification_and_refresh(
+        &self,
+        token: &str,
+    ) -> Result<Client<Auth>, AuthError> {
+        self.confirm_verification(&self.state.collection, token)
+            .await?;
+        self.auth_refresh().await
+    }
+
+    /// As a superuser, get a client authenticated as the `record_id` record
+    /// of `collection`, with that record's permissions. The token is valid
+    /// for `duration` (the collection's default when zero) and can't be
+    /// refreshed.
+    pub async fn impersonate(
+        &self,
+        collection: &str,
+        record_id: &str,
+        duration: Duration,
+    ) -> Result<Client<Auth>, AuthError> {
+        let url = self.endpoint(&format!(
+            "collections/{}/impersonate/{}",
+            collection, record_id
+        ));
+        let payload = json!({ "duration": duration.as_secs() });
+
+        let response = Httpc::post(self, &url, payload.to_string()).await?;
+        let impersonated = self.read_auth_response(collection, response).await?;
+        // Impersonation tokens can't be refreshed, so a 401 is final.
+        Ok(Client {
+            session: None,
+            ..impersonated
+        })
+    }
+}
+
+impl<A> Client<A> {
+    /// Run `request`, first aborting any pending request started with the
+    /// same `key`. Without a key the request just runs.
+    pub(crate) async fn run_keyed<T, E>(
+        &self,
+        key: Option<&str>,
+        request: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E>
+    where
+        E: From<anyhow::Error>,
+    {
+        let Some(key) = key else {
+            return request.await;
+        };
+        let (request, handle) = abortable(request);
+        let id = self.request_keys.next_id.fetch_add(1, Ordering::Relaxed);
+        let older = self
+            .request_keys
+            .pending
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (id, handle));
+        if let Some((_, older)) = older {
+            older.abort();
+        }
+
+        let _pending = PendingKey {
+            keys: &self.request_keys,
+            key,
+            id,
+        };
+        match request.await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::Error::from(RequestAbortedError {
+                request_key: key.to_string(),
+            })
+            .into()),
+        }
+    }
+
+    /// A copy of this client carrying the token issued for `collection`.
+    pub(crate) fn authenticated(&self, collection: &str, auth: AuthResult) -> Client<Auth> {
+        Client {
+            base_url: self.base_url.clone(),
+            api_prefix: self.api_prefix.clone(),
+            state: Auth {
+                collection: collection.to_string(),
+                record: auth.record,
+                superuser: collection == SUPERUSERS_COLLECTION,
+            },
+            auth_token: Some(auth.token),
+            http_client: self.http_client.clone(),
+            retry_policy: self.retry_policy.clone(),
+            timeout: self.timeout,
+            auth_scheme: self.auth_scheme,
+            default_headers: self.default_headers.clone(),
+            max_per_page: self.max_per_page,
+            max_body_size: self.max_body_size,
+            connection: self.connection.clone(),
+            request_keys: Arc::clone(&self.request_keys),
+            observer: self.observer.clone(),
+            transport: self.transport.clone(),
+            session: Some(Arc::new(Session {
+                refresh_path: refresh_path(collection),
+                refreshed_token: RwLock::new(None),
+            })),
+        }
+    }
+
+    /// The token requests are sent with, including one obtained by an
+    /// automatic refresh after a 401.
+    pub fn auth_token(&self) -> Option<String> {
+        self.current_token()
+    }
+
+    /// The token to send: the latest automatically refreshed one, if any.
+    pub(crate) fn current_token(&self) -> Option<String> {
+        let refreshed = self
+            .session
+            .as_ref()
+            .and_then(|session| session.refreshed_token.read().unwrap().clone());
+        refreshed.or_else(|| self.auth_token.clone())
+    }
+
+    async fn read_auth_response(
+        &self,
+        collection: &str,
+        response: Response,
+    ) -> Result<Client<Auth>, AuthError> {
         match response.status() {
             StatusCode::OK => {
-                let raw_response = response.json::<AuthSuccessResponse>().await?;
-                Ok(Client {
-                    base_url: self.base_url.clone(),
-                    state: Auth,
-                    auth_token: Some(raw_response.token),
-                })
+                let auth = response.json::<AuthResult>().await?;
+                Ok(self.authenticated(collection, auth))
             }
 
-            status if status.is_client_error() => {
-                let err_body = response.json::<ErrorResponse>().await?;
-                Err(AuthError::Validation(err_body))
-            }
+            _ => Err(auth_error(response).await),
+        }
+    }
 
-            other => {
-                let text = response.text().await.unwrap_or_else(|_| "<no body>".into());
-                Err(AuthError::Other(format!(
-                    "Unexpected status {} with body: {}",
-                    other, text
-                )))
-            }
+    async fn read_empty_response(&self, response: Response) -> Result<(), AuthError> {
+        if response.status().is_success() {
+            return Ok(());
         }
+        Err(auth_error(response).await)
     }
-}
\ No newline at end of file
+}
+
+/// Map a failed auth response to [`AuthError::Validation`] for client errors
+/// with a PocketBase error body and [`AuthError::Server`] for anything else.
+async fn auth_error(response: Response) -> AuthError {
+    let status = response.status();
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => return e.into(),
+    };
+    if status.is_client_error() {
+        if let Ok(err_body) = serde_json::from_str::<ErrorResponse>(&body) {
+            return AuthError::Validation(err_body);
+        }
+    }
+    AuthError::Server {
+        status: status.as_u16(),
+        body,
+    }
+}
+
+/// The `auth-refresh` endpoint of `collection`.
+fn refresh_path(collection: &str) -> String {
+    format!("collections/{}/auth-refresh", collection)
+}
+
+fn required_env(name: &str) -> Result<String> {
+    env::var(name).map_err(|_| anyhow!("environment variable {} is not set", name))
+}
@@ -1,8 +1,104 @@
-use crate::client::{Client};
+use crate::client::{Auth, Client, ResponseMeta};
+use crate::error::{CollectionDestroyError, FieldValidationError};
 use crate::httpc::Httpc;
-use anyhow::Result;
+use crate::records::{
+    body_snippet, check_page_limit, has_next_page, is_last_page, stable_sort, unknown_total,
+    DEFAULT_MAX_PAGES,
+};
+use crate::retry::RetryPolicy;
+use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use reqwest::Response;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// The kind of a collection field, as PocketBase names it on the wire, e.g.
+/// `text` or `relation`. Kinds this crate doesn't know yet are kept as
+/// [`FieldType::Unknown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    #[default]
+    Text,
+    Number,
+    Bool,
+    Email,
+    Url,
+    Date,
+    Autodate,
+    Select,
+    Relation,
+    File,
+    Json,
+    Editor,
+    Password,
+    GeoPoint,
+    Unknown(String),
+}
+
+impl FieldType {
+    /// The wire name, e.g. `geoPoint`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::Number => "number",
+            FieldType::Bool => "bool",
+            FieldType::Email => "email",
+            FieldType::Url => "url",
+            FieldType::Date => "date",
+            FieldType::Autodate => "autodate",
+            FieldType::Select => "select",
+            FieldType::Relation => "relation",
+            FieldType::File => "file",
+            FieldType::Json => "json",
+            FieldType::Editor => "editor",
+            FieldType::Password => "password",
+            FieldType::GeoPoint => "geoPoint",
+            FieldType::Unknown(name) => name,
+        }
+    }
+}
+
+impl From<&str> for FieldType {
+    fn from(name: &str) -> Self {
+        match name {
+            "text" => FieldType::Text,
+            "number" => FieldType::Number,
+            "bool" => FieldType::Bool,
+            "email" => FieldType::Email,
+            "url" => FieldType::Url,
+            "date" => FieldType::Date,
+            "autodate" => FieldType::Autodate,
+            "select" => FieldType::Select,
+            "relation" => FieldType::Relation,
+            "file" => FieldType::File,
+            "json" => FieldType::Json,
+            "editor" => FieldType::Editor,
+            "password" => FieldType::Password,
+            "geoPoint" => FieldType::GeoPoint,
+            other => FieldType::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for FieldType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(FieldType::from(name.as_str()))
+    }
+}
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,17 +106,55 @@ pub struct Field {
     pub system: bool,
     pub id: String,
     pub name: String,
-    pub r#type: String,
+    pub r#type: FieldType,
     pub required: bool,
     pub unique: bool,
+    #[serde(default)]
+    pub options: Value,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+impl Field {
+    /// The allowed values of a `select` field, `None` for other field types.
+    pub fn select_values(&self) -> Option<Vec<String>> {
+        if self.r#type != FieldType::Select {
+            return None;
+        }
+        let values = self.options.get("values")?.as_array()?;
+        Some(
+            values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FieldDeclaration<'a> {
     pub name: &'a str,
-    pub r#type: &'a str,
+    pub r#type: FieldType,
     pub required: bool,
+    /// `autodate` fields only: set the value when the record is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<bool>,
+    /// `autodate` fields only: refresh the value whenever the record is updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_update: Option<bool>,
+}
+
+impl<'a> FieldDeclaration<'a> {
+    /// An `autodate` field managed by the server.
+    pub fn autodate(name: &'a str, on_create: bool, on_update: bool) -> Self {
+        FieldDeclaration {
+            name,
+            r#type: FieldType::Autodate,
+            required: false,
+            on_create: Some(on_create),
+            on_update: Some(on_update),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,10 +162,23 @@ pub struct FieldDeclaration<'a> {
 pub struct CollectionList {
     pub page: i32,
     pub per_page: i32,
+    /// `-1` when the server did not count the collections, as with `skip_total`
+    /// or list responses that omit `totalItems`.
+    #[serde(default = "unknown_total")]
     pub total_items: i32,
+    /// `-1` when the server did not count the collections.
+    #[serde(default = "unknown_total")]
+    pub total_pages: i32,
     pub items: Vec<Collection>,
 }
 
+impl CollectionList {
+    /// Whether another page can be requested.
+    pub fn has_next_page(&self) -> bool {
+        has_next_page(self.page, self.per_page, self.items.len(), self.total_pages)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Collection {
@@ -41,6 +188,72 @@ pub struct Collection {
     pub updated: DateTime<Utc>,
     pub name: String,
     pub schema: Vec<Field>,
+    #[serde(default)]
+    pub system: bool,
+    /// API rules: `None` (a `null` rule) is admin-only, `Some("")` is public
+    /// and anything else is a filter expression.
+    #[serde(default)]
+    pub list_rule: Option<String>,
+    #[serde(default)]
+    pub view_rule: Option<String>,
+    #[serde(default)]
+    pub create_rule: Option<String>,
+    #[serde(default)]
+    pub update_rule: Option<String>,
+    #[serde(default)]
+    pub delete_rule: Option<String>,
+}
+
+impl Collection {
+    /// Anyone may list records: the `listRule` is an empty string.
+    /// A `null` rule restricts listing to admins.
+    pub fn is_publicly_listable(&self) -> bool {
+        self.list_rule.as_deref() == Some("")
+    }
+
+    /// Anyone may view single records: the `viewRule` is an empty string.
+    pub fn is_publicly_viewable(&self) -> bool {
+        self.view_rule.as_deref() == Some("")
+    }
+
+    /// Check the `select` values in `record` against this schema before it
+    /// is sent, so an out-of-set value fails locally instead of with a 400.
+    /// Fields missing from `record`, `null` and empty values are not checked.
+    pub fn validate_record(&self, record: &Value) -> Result<(), FieldValidationError> {
+        for field in &self.schema {
+            let Some(allowed) = field.select_values() else {
+                continue;
+            };
+            let values = match record.get(&field.name) {
+                Some(Value::String(value)) => vec![value.as_str()],
+                Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+            for value in values {
+                if !value.is_empty() && !allowed.iter().any(|a| a == value) {
+                    return Err(FieldValidationError::InvalidSelectValue {
+                        field: field.name.clone(),
+                        value: value.to_string(),
+                        allowed: allowed.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every rule is `null`, so only admins can access the records.
+    pub fn is_admin_only(&self) -> bool {
+        [
+            &self.list_rule,
+            &self.view_rule,
+            &self.create_rule,
+            &self.update_rule,
+            &self.delete_rule,
+        ]
+        .iter()
+        .all(|rule| rule.is_none())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +289,29 @@ pub struct CollectionCreateRequestBuilder<'a, A> {
 pub struct CollectionViewRequestBuilder<'a, A> {
     pub client: &'a Client<A>,
     pub name: &'a str,
+    pub retry: Option<RetryPolicy>,
+}
+
+/// Partially updates a collection; only the properties that were set are sent,
+/// so everything else keeps its current value on the server.
+#[derive(Clone, Debug)]
+pub struct CollectionUpdateRequestBuilder<'a, A> {
+    pub client: &'a Client<A>,
+    pub id_or_name: &'a str,
+    pub name: Option<&'a str>,
+    pub new_fields: Vec<FieldDeclaration<'a>>,
+    pub list_rule: Option<Option<String>>,
+    pub view_rule: Option<Option<String>>,
+    pub create_rule: Option<Option<String>>,
+    pub update_rule: Option<Option<String>>,
+    pub delete_rule: Option<Option<String>>,
+    pub indexes: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CollectionDestroyRequestBuilder<'a, A> {
+    pub client: &'a Client<A>,
+    pub name: &'a str,
 }
 
 #[derive(Clone, Debug)]
@@ -89,11 +325,17 @@ where
     pub expand: Option<String>,
     pub per_page: i32,
     pub page: i32,
+    pub retry: Option<RetryPolicy>,
+    pub skip_total: bool,
+    /// Extra query parameters; see [`query_param`](Self::query_param).
+    pub extra_params: Vec<(String, String)>,
 }
 
 impl<'a, A: Clone> CollectionListRequestBuilder<'a, A> {
-    pub async fn call(&self) -> Result<CollectionList> {
-        let url = format!("{}/api/collections", self.client.base_url);
+    /// Send the list request and hand back the raw response without reading
+    /// its body, for custom streaming or header inspection.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.client.endpoint("collections");
         let mut build_opts: Vec<(&str, &str)> = Vec::new();
 
         if let Some(filter_opts) = &self.filter {
@@ -102,26 +344,39 @@ impl<'a, A: Clone> CollectionListRequestBuilder<'a, A> {
         if let Some(sort_opts) = &self.sort {
             build_opts.push(("sort", sort_opts))
         }
-        if let Some(expand_opts) = &self.expand {
+        if let Some(expand_opts) = self.expand.as_deref().filter(|e| !e.is_empty()) {
             build_opts.push(("expand", expand_opts))
         }
         let per_page_opts = self.per_page.to_string();
         let page_opts = self.page.to_string();
         build_opts.push(("perPage", per_page_opts.as_str()));
         build_opts.push(("page", page_opts.as_str()));
-
-        match Httpc::get(self.client, &url, Some(build_opts)).await {
-            Ok(result) => {
-                let response = result.json::<CollectionList>().await?;
-                Ok(response)
-            }
-            Err(e) => Err(e),
+        if self.skip_total {
+            build_opts.push(("skipTotal", "1"));
+        }
+        for (key, value) in &self.extra_params {
+            build_opts.push((key, value));
         }
+
+        Httpc::get_with(self.client, &url, Some(build_opts), self.retry.as_ref()).await
     }
 
-    pub fn filter(&self, filter_opts: String) -> Self {
+    pub async fn call(&self) -> Result<CollectionList> {
+        self.call_with_meta().await.map(|(_, list)| list)
+    }
+
+    /// Like [`call`](Self::call), also returning the response status and
+    /// headers, e.g. to inspect rate limits.
+    pub async fn call_with_meta(&self) -> Result<(ResponseMeta, CollectionList)> {
+        let result = self.send_raw().await?;
+        let meta = ResponseMeta::of(&result);
+        let response = result.json::<CollectionList>().await?;
+        Ok((meta, response))
+    }
+
+    pub fn filter(&self, filter_opts: impl Into<String>) -> Self {
         Self {
-            filter: Some(filter_opts),
+            filter: Some(filter_opts.into()),
             ..self.clone()
         }
     }
@@ -133,6 +388,25 @@ impl<'a, A: Clone> CollectionListRequestBuilder<'a, A> {
         }
     }
 
+    /// Append an extra `key=value` query parameter, sent after the typed
+    /// ones, for parameters this builder doesn't model.
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        let mut extra_params = self.extra_params.clone();
+        extra_params.push((key.to_string(), value.into()));
+        Self {
+            extra_params,
+            ..self.clone()
+        }
+    }
+
+    /// Skip counting the matching collections; `total_items` is then not meaningful.
+    pub fn skip_total(&self, skip_total: bool) -> Self {
+        Self {
+            skip_total,
+            ..self.clone()
+        }
+    }
+
     pub fn page(&self, page_count: i32) -> Self {
         Self {
             page: page_count,
@@ -140,9 +414,10 @@ impl<'a, A: Clone> CollectionListRequestBuilder<'a, A> {
         }
     }
 
-    pub fn sort(&self, sort_opts: String) -> Self {
+    /// Accepts a raw sort string or a [`Sort`](crate::sort::Sort).
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
         Self {
-            sort: Some(sort_opts),
+            sort: Some(sort_opts.into()),
             ..self.clone()
         }
     }
@@ -153,17 +428,165 @@ impl<'a, A: Clone> CollectionListRequestBuilder<'a, A> {
             ..self.clone()
         }
     }
+
+    /// Override the client's retry policy for this request.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
+}
+
+impl<'a> CollectionCreateRequestBuilder<'a, Auth> {
+    /// Create the collection. Requires a superuser client, see
+    /// [`Client::auth_as_admin`].
+    pub async fn call(&self) -> Result<Collection> {
+        if !self.client.state.superuser {
+            return Err(anyhow!("creating collections requires superuser auth"));
+        }
+        let details = self.collection_details.as_ref().ok_or_else(|| {
+            anyhow!(
+                "collection details for `{}` are not set; use .schema(), .details() or a rule setter first",
+                self.collection_name
+            )
+        })?;
+        let url = self.client.endpoint("collections");
+        let payload = serde_json::to_string(details)?;
+        let resp = Httpc::post(self.client, &url, payload).await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .with_context(|| format!("Reading response body from {} failed", url))?;
+
+        if !status.is_success() {
+            return Err(anyhow!(
+                "Request to {} failed: HTTP {}.\nResponse (truncated):\n{}",
+                url,
+                status.as_u16(),
+                body_snippet(&body)
+            ));
+        }
+
+        Ok(serde_json::from_str::<Collection>(&body)?)
+    }
+}
+
+impl<'a, A: Clone> CollectionCreateRequestBuilder<'a, A> {
+    fn current_details(&self) -> CollectionDetails<'a> {
+        self.collection_details
+            .clone()
+            .unwrap_or_else(|| CollectionDetails {
+                id: None,
+                name: Some(self.collection_name),
+                r#type: Some("base"),
+                schema: Vec::new(),
+                system: false,
+                list_rule: None,
+                view_rule: None,
+                create_rule: None,
+                update_rule: None,
+                delete_rule: None,
+                indexes: Vec::new(),
+            })
+    }
+
+    pub fn details(&self, details: CollectionDetails<'a>) -> Self {
+        Self {
+            collection_details: Some(details),
+            ..self.clone()
+        }
+    }
+
+    pub fn collection_type(&self, collection_type: &'a str) -> Self {
+        self.details(CollectionDetails {
+            r#type: Some(collection_type),
+            ..self.current_details()
+        })
+    }
+
+    pub fn schema(&self, schema: Vec<FieldDeclaration<'a>>) -> Self {
+        self.details(CollectionDetails {
+            schema,
+            ..self.current_details()
+        })
+    }
+
+    /// Add the standard `created` timestamp, set once when a record is created.
+    pub fn created_autodate(&self) -> Self {
+        self.push_field(FieldDeclaration::autodate("created", true, false))
+    }
+
+    /// Add the standard `updated` timestamp, refreshed on every create and update.
+    pub fn updated_autodate(&self) -> Self {
+        self.push_field(FieldDeclaration::autodate("updated", true, true))
+    }
+
+    fn push_field(&self, field: FieldDeclaration<'a>) -> Self {
+        let mut details = self.current_details();
+        details.schema.push(field);
+        self.details(details)
+    }
+
+    pub fn indexes(&self, indexes: Vec<String>) -> Self {
+        self.details(CollectionDetails {
+            indexes,
+            ..self.current_details()
+        })
+    }
+
+    pub fn list_rule(&self, rule: &str) -> Self {
+        self.details(CollectionDetails {
+            list_rule: Some(rule.to_string()),
+            ..self.current_details()
+        })
+    }
+
+    pub fn view_rule(&self, rule: &str) -> Self {
+        self.details(CollectionDetails {
+            view_rule: Some(rule.to_string()),
+            ..self.current_details()
+        })
+    }
+
+    pub fn create_rule(&self, rule: &str) -> Self {
+        self.details(CollectionDetails {
+            create_rule: Some(rule.to_string()),
+            ..self.current_details()
+        })
+    }
+
+    pub fn update_rule(&self, rule: &str) -> Self {
+        self.details(CollectionDetails {
+            update_rule: Some(rule.to_string()),
+            ..self.current_details()
+        })
+    }
+
+    pub fn delete_rule(&self, rule: &str) -> Self {
+        self.details(CollectionDetails {
+            delete_rule: Some(rule.to_string()),
+            ..self.current_details()
+        })
+    }
 }
 
 impl<'a, A: Clone> CollectionsManager<'a, A> {
-    pub fn view(&self, name: &'a str) -> CollectionViewRequestBuilder<A> {
+    pub fn view(&self, name: &'a str) -> CollectionViewRequestBuilder<'a, A> {
         CollectionViewRequestBuilder {
             client: self.client,
             name,
+            retry: None,
         }
     }
 
-    pub fn create(&self, name: &'a str) -> CollectionCreateRequestBuilder<A> {
+    pub fn create(&self, name: &'a str) -> CollectionCreateRequestBuilder<'a, A> {
         CollectionCreateRequestBuilder {
             client: self.client,
             collection_details: None,
@@ -171,7 +594,58 @@ impl<'a, A: Clone> CollectionsManager<'a, A> {
         }
     }
 
-    pub fn list(&self) -> CollectionListRequestBuilder<A> {
+    pub fn update(&self, id_or_name: &'a str) -> CollectionUpdateRequestBuilder<'a, A> {
+        CollectionUpdateRequestBuilder {
+            client: self.client,
+            id_or_name,
+            name: None,
+            new_fields: Vec::new(),
+            list_rule: None,
+            view_rule: None,
+            create_rule: None,
+            update_rule: None,
+            delete_rule: None,
+            indexes: None,
+        }
+    }
+
+    pub fn delete(&self, name: &'a str) -> CollectionDestroyRequestBuilder<'a, A> {
+        CollectionDestroyRequestBuilder {
+            client: self.client,
+            name,
+        }
+    }
+
+    /// Fetch every collection, paging until the listing ends. Collections
+    /// are sorted by `id` so none are repeated or skipped across pages.
+    pub async fn get_all(&self) -> Result<Vec<Collection>> {
+        let per_page = self.client.per_page_limit();
+        let list = self.list().sort(stable_sort(None)).per_page(per_page);
+
+        let mut all_items = Vec::new();
+        let mut page = 1;
+        loop {
+            let page_resp = list.page(page).call().await?;
+            let last_page = is_last_page(
+                page_resp.page,
+                page_resp.items.len(),
+                page_resp.total_items,
+                all_items.len() + page_resp.items.len(),
+                per_page,
+            );
+            all_items.extend(page_resp.items);
+
+            if last_page {
+                break;
+            }
+            check_page_limit(page, DEFAULT_MAX_PAGES, all_items.len())?;
+            page += 1;
+        }
+
+        Ok(all_items)
+    }
+
+    pub fn list(&self) -> CollectionListRequestBuilder<'a, A> {
         CollectionListRequestBuilder {
             client: self.client,
             filter: None,
@@ -179,19 +653,206 @@ impl<'a, A: Clone> CollectionsManager<'a, A> {
             expand: None,
             per_page: 100,
             page: 1,
+            retry: None,
+            skip_total: false,
+            extra_params: Vec::new(),
+        }
+    }
+}
+
+impl<'a, A: Clone> CollectionViewRequestBuilder<'a, A> {
+    /// Override the client's retry policy for this request.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
         }
     }
+
+    pub fn no_retry(&self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
 }
 
 impl<'a, A> CollectionViewRequestBuilder<'a, A> {
+    /// Send the view request and hand back the raw response without reading
+    /// its body, for custom streaming or header inspection.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.client.endpoint(&format!("collections/{}", self.name));
+        Httpc::get_with(self.client, &url, None, self.retry.as_ref()).await
+    }
+
+    pub async fn call(&self) -> Result<Collection> {
+        self.call_with_meta()
+            .await
+            .map(|(_, collection)| collection)
+    }
+
+    /// Like [`call`](Self::call), also returning the response status and
+    /// headers, e.g. to inspect rate limits.
+    pub async fn call_with_meta(&self) -> Result<(ResponseMeta, Collection)> {
+        let result = self.send_raw().await?;
+        let meta = ResponseMeta::of(&result);
+        let response = result.json::<Collection>().await?;
+        Ok((meta, response))
+    }
+}
+
+impl<'a> CollectionDestroyRequestBuilder<'a, Auth> {
+    /// Delete the collection. Requires a superuser client, see
+    /// [`Client::auth_as_admin`].
+    pub async fn call(&self) -> Result<(), CollectionDestroyError> {
+        if !self.client.state.superuser {
+            return Err(CollectionDestroyError::NotSuperuser);
+        }
+        let url = self.client.endpoint(&format!("collections/{}", self.name));
+        let resp = Httpc::delete(self.client, &url).await?;
+
+        match resp.status().as_u16() {
+            204 => Ok(()),
+            404 => Err(CollectionDestroyError::NotFound {
+                name: self.name.to_string(),
+            }),
+            status => {
+                let body = resp
+                    .text()
+                    .await
+                    .with_context(|| format!("Reading response body from {} failed", url))?;
+                Err(CollectionDestroyError::Http {
+                    status,
+                    url,
+                    body_snippet: body_snippet(&body).to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl<'a, A: Clone> CollectionUpdateRequestBuilder<'a, A> {
     pub async fn call(&self) -> Result<Collection> {
-        let url = format!("{}/api/collections/{}", self.client.base_url, self.name);
-        match Httpc::get(self.client, &url, None).await {
-            Ok(result) => {
-                let response = result.json::<Collection>().await?;
-                Ok(response)
+        let url = self
+            .client
+            .endpoint(&format!("collections/{}", self.id_or_name));
+        let mut body = Map::new();
+
+        if let Some(name) = self.name {
+            body.insert("name".into(), Value::from(name));
+        }
+        let rules = [
+            ("listRule", &self.list_rule),
+            ("viewRule", &self.view_rule),
+            ("createRule", &self.create_rule),
+            ("updateRule", &self.update_rule),
+            ("deleteRule", &self.delete_rule),
+        ];
+        for (key, rule) in rules {
+            if let Some(rule) = rule {
+                body.insert(key.into(), serde_json::to_value(rule)?);
+            }
+        }
+        if let Some(indexes) = &self.indexes {
+            body.insert("indexes".into(), serde_json::to_value(indexes)?);
+        }
+        if !self.new_fields.is_empty() {
+            // PocketBase replaces the whole schema, so keep the existing fields.
+            let current = CollectionViewRequestBuilder {
+                client: self.client,
+                name: self.id_or_name,
+                retry: None,
             }
-            Err(e) => Err(e),
+            .call()
+            .await?;
+            let mut schema = Vec::new();
+            for field in &current.schema {
+                schema.push(serde_json::to_value(field)?);
+            }
+            for field in &self.new_fields {
+                schema.push(serde_json::to_value(field)?);
+            }
+            body.insert("schema".into(), Value::Array(schema));
+        }
+
+        let resp = Httpc::patch(self.client, &url, Value::Object(body).to_string()).await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .with_context(|| format!("Reading response body from {} failed", url))?;
+
+        if !status.is_success() {
+            return Err(anyhow!(
+                "Request to {} failed: HTTP {}.\nResponse (truncated):\n{}",
+                url,
+                status.as_u16(),
+                body_snippet(&body)
+            ));
+        }
+
+        Ok(serde_json::from_str::<Collection>(&body)?)
+    }
+
+    pub fn name(&self, name: &'a str) -> Self {
+        Self {
+            name: Some(name),
+            ..self.clone()
+        }
+    }
+
+    /// Append a field to the collection's existing schema.
+    pub fn add_field(&self, field: FieldDeclaration<'a>) -> Self {
+        let mut new_fields = self.new_fields.clone();
+        new_fields.push(field);
+        Self {
+            new_fields,
+            ..self.clone()
+        }
+    }
+
+    pub fn indexes(&self, indexes: Vec<String>) -> Self {
+        Self {
+            indexes: Some(indexes),
+            ..self.clone()
+        }
+    }
+
+    /// Set the list rule; `None` restricts the action to superusers.
+    pub fn list_rule(&self, rule: Option<&str>) -> Self {
+        Self {
+            list_rule: Some(rule.map(str::to_string)),
+            ..self.clone()
+        }
+    }
+
+    /// Set the view rule; `None` restricts the action to superusers.
+    pub fn view_rule(&self, rule: Option<&str>) -> Self {
+        Self {
+            view_rule: Some(rule.map(str::to_string)),
+            ..self.clone()
+        }
+    }
+
+    /// Set the create rule; `None` restricts the action to superusers.
+    pub fn create_rule(&self, rule: Option<&str>) -> Self {
+        Self {
+            create_rule: Some(rule.map(str::to_string)),
+            ..self.clone()
+        }
+    }
+
+    /// Set the update rule; `None` restricts the action to superusers.
+    pub fn update_rule(&self, rule: Option<&str>) -> Self {
+        Self {
+            update_rule: Some(rule.map(str::to_string)),
+            ..self.clone()
+        }
+    }
+
+    /// Set the delete rule; `None` restricts the action to superusers.
+    pub fn delete_rule(&self, rule: Option<&str>) -> Self {
+        Self {
+            delete_rule: Some(rule.map(str::to_string)),
+            ..self.clone()
         }
     }
 }
@@ -1,3 +1,5 @@
+use crate::client::ErrorResponse;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,6 +21,11 @@ pub enum RecordViewError {
         body_snippet: String,
     },
 
+    /// The server is rate limiting requests (HTTP 429); `retry_after` is
+    /// taken from the `Retry-After` header when given in seconds.
+    #[error("rate limited (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
     /// JSON decode error with precise path from serde_path_to_error.
     #[error("json decode error at `{path}`: {source}")]
     Decode {
@@ -32,3 +39,186 @@ pub enum RecordViewError {
     #[error("transport error: {0}")]
     Transport(#[from] anyhow::Error),
 }
+
+#[derive(Debug, Error)]
+pub enum RecordListError {
+    /// Non-2xx HTTP error, e.g. an invalid filter (HTTP 400).
+    #[error("http error {status} for {url}: {body_snippet}")]
+    Http {
+        status: u16,
+        url: String,
+        body_snippet: String,
+    },
+
+    /// The server is rate limiting requests (HTTP 429); `retry_after` is
+    /// taken from the `Retry-After` header when given in seconds.
+    #[error("rate limited (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// JSON decode error with precise path from serde_path_to_error, e.g.
+    /// `items[3].title`.
+    #[error("json decode error at `{path}`: {source}")]
+    Decode {
+        path: String,
+        #[source]
+        source: serde_path_to_error::Error<serde_json::Error>,
+        body_snippet: String,
+    },
+
+    /// Transport or unexpected lower-level error.
+    #[error("transport error: {0}")]
+    Transport(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RecordWriteError {
+    /// The server rejected the record (HTTP 4xx, or a 2xx carrying an error
+    /// body); `data` maps each invalid field to its error.
+    #[error("record rejected: {0}")]
+    Validation(ErrorResponse),
+
+    /// The record failed client-side schema validation and was not sent.
+    #[error(transparent)]
+    InvalidField(#[from] FieldValidationError),
+
+    /// Other non-2xx HTTP error.
+    #[error("http error {status} for {url}: {body_snippet}")]
+    Http {
+        status: u16,
+        url: String,
+        body_snippet: String,
+    },
+
+    /// The server is rate limiting requests (HTTP 429); `retry_after` is
+    /// taken from the `Retry-After` header when given in seconds.
+    #[error("rate limited (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// The server answered with success but no body, e.g. HTTP 204.
+    #[error("http {status} for {url} returned no body")]
+    EmptyBody { status: u16, url: String },
+
+    /// The success response could not be decoded, e.g. an HTML page from a
+    /// reverse proxy.
+    #[error("json decode error: {source}: {body_snippet}")]
+    Decode {
+        #[source]
+        source: serde_json::Error,
+        /// Optional truncated body to aid debugging.
+        body_snippet: String,
+    },
+
+    /// Transport or unexpected lower-level error.
+    #[error("transport error: {0}")]
+    Transport(#[from] anyhow::Error),
+}
+
+/// The server is rate limiting requests (HTTP 429). Returned inside
+/// `anyhow::Error` by streaming list and delete calls; recover it with
+/// `downcast_ref`.
+#[derive(Debug, Error)]
+#[error("request to {url} was rate limited (retry after {retry_after:?})")]
+pub struct RateLimitedError {
+    pub url: String,
+    /// From the `Retry-After` header, when given in seconds.
+    pub retry_after: Option<Duration>,
+}
+
+/// Paging reached its page cap before the last page, so the items fetched so
+/// far are incomplete and were discarded.
+#[derive(Debug, Error)]
+#[error("stopped paging after {max_pages} pages ({fetched} items) before reaching the last page")]
+pub struct PageLimitError {
+    pub max_pages: i32,
+    /// Items fetched before giving up.
+    pub fetched: usize,
+}
+
+/// Paging was stopped by the list request's cancellation token.
+#[cfg(feature = "cancellation")]
+#[derive(Debug, Error)]
+#[error("cancelled after fetching {fetched} records")]
+pub struct CancelledError {
+    /// Records fetched before the cancellation.
+    pub fetched: usize,
+}
+
+/// A request was aborted because a newer one with the same request key was
+/// started; see `request_key` on the record list and view builders.
+#[derive(Debug, Error)]
+#[error("request `{request_key}` was cancelled by a newer request with the same key")]
+pub struct RequestAbortedError {
+    pub request_key: String,
+}
+
+/// A filter string value ends in `\`, which PocketBase would read as
+/// escaping the closing quote, letting the rest of the filter into the value.
+#[derive(Debug, Error)]
+#[error("filter value {value:?} ends in a backslash and can't be quoted")]
+pub struct InvalidFilterValue {
+    pub value: String,
+}
+
+/// A request did not complete within the client's configured timeout.
+#[derive(Debug, Error)]
+#[error("request to {url} timed out after {after:?}")]
+pub struct TimeoutError {
+    pub url: String,
+    pub after: Duration,
+}
+
+/// A response body was larger than the client's configured maximum.
+#[derive(Debug, Error)]
+#[error("response body from {url} exceeds the limit of {limit} bytes")]
+pub struct BodyTooLargeError {
+    pub url: String,
+    pub limit: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum CollectionDestroyError {
+    /// The collection was not found (HTTP 404), e.g. it was already deleted.
+    #[error("collection not found: '{name}'")]
+    NotFound { name: String },
+
+    /// The client is not authenticated as a superuser.
+    #[error("deleting collections requires superuser auth")]
+    NotSuperuser,
+
+    /// Other non-2xx HTTP error.
+    #[error("http error {status} for {url}: {body_snippet}")]
+    Http {
+        status: u16,
+        url: String,
+        body_snippet: String,
+    },
+
+    /// Transport or unexpected lower-level error.
+    #[error("transport error: {0}")]
+    Transport(#[from] anyhow::Error),
+}
+
+/// A single list item that could not be decoded into the requested type.
+#[derive(Debug, Error)]
+#[error("item {index} (id: {id:?}) failed to decode: {source}")]
+pub struct ItemError {
+    /// Position of the item across all fetched pages.
+    pub index: usize,
+    /// The record id, when the raw item carried one.
+    pub id: Option<String>,
+    /// The raw item as returned by the server.
+    pub raw: serde_json::Value,
+    #[source]
+    pub source: serde_json::Error,
+}
+
+#[derive(Debug, Error)]
+pub enum FieldValidationError {
+    /// A `select` field was given a value outside its allowed set.
+    #[error("invalid value '{value}' for select field '{field}', expected one of {allowed:?}")]
+    InvalidSelectValue {
+        field: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+}
@@ -0,0 +1,252 @@
+use crate::error::InvalidFilterValue;
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// A PocketBase filter expression, built from typed comparisons so values are
+/// always quoted and escaped correctly. Comparisons fail with
+/// [`InvalidFilterValue`] for values that can't be quoted safely.
+///
+/// ```
+/// use pocketbase_client::filter::Filter;
+///
+/// let filter = Filter::eq("status", "active")?.and(Filter::gt("age", 18)?);
+/// assert_eq!(filter.to_string(), "(status = 'active' && age > 18)");
+/// # Ok::<(), pocketbase_client::error::InvalidFilterValue>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter(String);
+
+/// A value that can appear on the right-hand side of a filter comparison.
+pub trait FilterValue {
+    /// Render the value as a PocketBase filter literal.
+    fn to_literal(&self) -> String;
+
+    /// Whether the value can be rendered without changing the meaning of the
+    /// surrounding filter.
+    fn check(&self) -> Result<(), InvalidFilterValue> {
+        Ok(())
+    }
+}
+
+/// Only `'` is escaped, as PocketBase unescapes nothing else; backslashes
+/// are sent as they are. A string ending in `\` fails [`check`](FilterValue::check),
+/// since the server would read the closing quote after it as escaped.
+impl FilterValue for str {
+    fn to_literal(&self) -> String {
+        format!("'{}'", self.replace('\'', "\\'"))
+    }
+
+    fn check(&self) -> Result<(), InvalidFilterValue> {
+        if self.ends_with('\\') {
+            return Err(InvalidFilterValue {
+                value: self.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl FilterValue for String {
+    fn to_literal(&self) -> String {
+        self.as_str().to_literal()
+    }
+
+    fn check(&self) -> Result<(), InvalidFilterValue> {
+        self.as_str().check()
+    }
+}
+
+/// Dates are compared in PocketBase's `2006-01-02 15:04:05.000Z` format.
+impl FilterValue for DateTime<Utc> {
+    fn to_literal(&self) -> String {
+        format!("'{}'", self.format("%Y-%m-%d %H:%M:%S%.3fZ"))
+    }
+}
+
+impl<T: FilterValue + ?Sized> FilterValue for &T {
+    fn to_literal(&self) -> String {
+        (**self).to_literal()
+    }
+
+    fn check(&self) -> Result<(), InvalidFilterValue> {
+        (**self).check()
+    }
+}
+
+impl<T: FilterValue> FilterValue for Option<T> {
+    fn to_literal(&self) -> String {
+        match self {
+            Some(value) => value.to_literal(),
+            None => "null".to_string(),
+        }
+    }
+
+    fn check(&self) -> Result<(), InvalidFilterValue> {
+        self.as_ref().map_or(Ok(()), FilterValue::check)
+    }
+}
+
+macro_rules! display_filter_value {
+    ($($ty:ty),*) => {
+        $(impl FilterValue for $ty {
+            fn to_literal(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+display_filter_value!(bool, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, f32, f64);
+
+/// `value` as a quoted filter string literal with its `'` escaped, for
+/// interpolating untrusted input into a hand-written filter. Fails for values
+/// ending in `\`, which would escape the closing quote.
+///
+/// ```
+/// use pocketbase_client::filter::quote;
+///
+/// assert_eq!(quote("it's").unwrap(), r"'it\'s'");
+/// assert_eq!(quote(r"C:\tmp").unwrap(), r"'C:\tmp'");
+/// assert!(quote(r"C:\tmp\").is_err());
+/// ```
+pub fn quote(value: &str) -> Result<String, InvalidFilterValue> {
+    value.check()?;
+    Ok(value.to_literal())
+}
+
+impl Filter {
+    fn compare(
+        field: &str,
+        operator: &str,
+        value: impl FilterValue,
+    ) -> Result<Self, InvalidFilterValue> {
+        value.check()?;
+        Ok(Filter(format!(
+            "{} {} {}",
+            field,
+            operator,
+            value.to_literal()
+        )))
+    }
+
+    /// `expression` with each `{:name}` placeholder replaced by the escaped
+    /// literal of the matching param, like `pb.filter` in the JS SDK.
+    /// Placeholders without a param are left as they are. Fails if a param
+    /// can't be quoted safely, e.g. a string ending in `\`.
+    ///
+    /// ```
+    /// use pocketbase_client::filter::Filter;
+    ///
+    /// let name = "O'Brien";
+    /// let filter = Filter::with_params(
+    ///     "name = {:name} && age > {:age}",
+    ///     &[("name", &name), ("age", &18)],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(filter.to_string(), r"name = 'O\'Brien' && age > 18");
+    /// ```
+    pub fn with_params(
+        expression: &str,
+        params: &[(&str, &dyn FilterValue)],
+    ) -> Result<Self, InvalidFilterValue> {
+        for (_, value) in params {
+            value.check()?;
+        }
+        let mut filter = String::with_capacity(expression.len());
+        let mut rest = expression;
+        while let Some(start) = rest.find("{:") {
+            filter.push_str(&rest[..start]);
+            let placeholder = &rest[start..];
+            let param = placeholder.find('}').and_then(|end| {
+                let name = &placeholder[2..end];
+                let (_, value) = params.iter().find(|(key, _)| *key == name)?;
+                Some((end, value.to_literal()))
+            });
+            match param {
+                Some((end, literal)) => {
+                    filter.push_str(&literal);
+                    rest = &placeholder[end + 1..];
+                }
+                None => {
+                    filter.push_str("{:");
+                    rest = &placeholder[2..];
+                }
+            }
+        }
+        filter.push_str(rest);
+        Ok(Filter(filter))
+    }
+
+    /// An expression used as is, e.g. `@request.auth.id != ''`.
+    pub fn raw(expression: &str) -> Self {
+        Filter(expression.to_string())
+    }
+
+    pub fn eq(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "=", value)
+    }
+
+    pub fn ne(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "!=", value)
+    }
+
+    pub fn gt(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, ">", value)
+    }
+
+    pub fn gte(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, ">=", value)
+    }
+
+    pub fn lt(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "<", value)
+    }
+
+    pub fn lte(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "<=", value)
+    }
+
+    /// `field ~ value`: contains, or a `%` wildcard match.
+    pub fn like(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "~", value)
+    }
+
+    pub fn not_like(field: &str, value: impl FilterValue) -> Result<Self, InvalidFilterValue> {
+        Self::compare(field, "!~", value)
+    }
+
+    /// Auth records whose email is verified (`verified = true`).
+    pub fn verified() -> Self {
+        Self::raw("verified = true")
+    }
+
+    /// Auth records whose email is not verified yet.
+    pub fn unverified() -> Self {
+        Self::raw("verified = false")
+    }
+
+    /// The auth record with `email`.
+    pub fn email(email: &str) -> Result<Self, InvalidFilterValue> {
+        Self::eq("email", email)
+    }
+
+    pub fn and(self, other: Filter) -> Self {
+        Filter(format!("({} && {})", self.0, other.0))
+    }
+
+    pub fn or(self, other: Filter) -> Self {
+        Filter(format!("({} || {})", self.0, other.0))
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Filter> for String {
+    fn from(filter: Filter) -> Self {
+        filter.0
+    }
+}
@@ -1,6 +1,11 @@
-use crate::client::Client as UserClient;
-use anyhow::Result;
-use reqwest::{Client as ReqwestClient, Response};
+use crate::client::{AuthResult, Client as UserClient, Session};
+use crate::error::TimeoutError;
+use crate::observer::RequestEvent;
+use crate::retry::RetryPolicy;
+use anyhow::{anyhow, Context, Result};
+use reqwest::header::{HeaderValue, AUTHORIZATION, RETRY_AFTER};
+use reqwest::{multipart::Form, Method, Request, RequestBuilder, Response, StatusCode};
+use std::time::{Duration, Instant};
 
 pub struct Httpc;
 
@@ -9,28 +14,223 @@ impl Httpc {
         builder: reqwest::RequestBuilder,
         client: &UserClient<T>,
     ) -> reqwest::RequestBuilder {
-        if let Some(token) = client.auth_token.as_ref() {
-            builder.header("Authorization", token.as_str())
+        if let Some(token) = client.current_token() {
+            builder.header(AUTHORIZATION, client.auth_scheme.header_value(&token))
         } else {
             builder
         }
     }
 
+    /// Start a request to `url`. Every verb goes through here so the auth
+    /// header is attached uniformly.
+    fn request<T>(client: &UserClient<T>, method: Method, url: &str) -> RequestBuilder {
+        Self::attach_auth_info(client.http_client.request(method, url), client)
+    }
+
+    /// Add the client's default headers that `request` does not set itself.
+    fn with_default_headers<T>(
+        client: &UserClient<T>,
+        request: RequestBuilder,
+    ) -> Result<RequestBuilder> {
+        if client.default_headers.is_empty() {
+            return Ok(request);
+        }
+        let (http, request) = request.build_split();
+        let mut request = request?;
+        let headers = request.headers_mut();
+        for name in client.default_headers.keys() {
+            if !headers.contains_key(name) {
+                for value in client.default_headers.get_all(name) {
+                    headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+        Ok(RequestBuilder::from_parts(http, request))
+    }
+
+    /// Send `request` with the client's timeout, retrying per `retry`.
+    ///
+    /// When an authenticated request is answered with 401 the token is
+    /// refreshed and the request retried once; if the refresh fails the
+    /// original response is returned. Requests whose body cannot be replayed,
+    /// such as multipart uploads, are not retried.
+    async fn send<T>(
+        client: &UserClient<T>,
+        request: RequestBuilder,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let session = client
+            .session
+            .as_ref()
+            .filter(|_| client.auth_token.is_some());
+        let Some((session, replay)) = session.zip(request.try_clone()) else {
+            return Self::send_once(client, request, retry).await;
+        };
+
+        let response = Self::send_once(client, request, retry).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let token = match Self::refresh_token(client, session).await {
+            Ok(token) => token,
+            Err(e) => {
+                log::warn!("refreshing the auth token after a 401 failed: {:#}", e);
+                return Ok(response);
+            }
+        };
+
+        let (http, replay) = replay.build_split();
+        let mut replay = replay?;
+        replay.headers_mut().insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&client.auth_scheme.header_value(&token))?,
+        );
+        Self::send_once(client, RequestBuilder::from_parts(http, replay), retry).await
+    }
+
+    /// Exchange the client's current token for a fresh one and share it with
+    /// the client's clones through `session`.
+    async fn refresh_token<T>(client: &UserClient<T>, session: &Session) -> Result<String> {
+        let url = client.endpoint(&session.refresh_path);
+        let request = Self::request(client, Method::POST, &url);
+        let response = Self::send_once(client, request, None).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "POST {} failed: HTTP {}",
+                url,
+                response.status().as_u16()
+            ));
+        }
+        let auth = response.json::<AuthResult>().await?;
+        *session.refreshed_token.write().unwrap() = Some(auth.token.clone());
+        Ok(auth.token)
+    }
+
+    async fn send_once<T>(
+        client: &UserClient<T>,
+        request: RequestBuilder,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let request = Self::with_default_headers(client, request)?;
+        let request = match client.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        let request = request.build()?;
+        let method = request.method().clone();
+        let url = request.url().to_string();
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "pocketbase.request",
+            method = %method,
+            url = %url,
+            status = tracing::field::Empty,
+        );
+        let started = Instant::now();
+        let sent = Self::send_retrying(client, request, retry);
+        #[cfg(feature = "tracing")]
+        let sent = tracing::Instrument::instrument(sent, span.clone());
+        let outcome = sent.await;
+
+        let status = outcome.as_ref().ok().map(Response::status);
+        #[cfg(feature = "tracing")]
+        if let Some(status) = status {
+            span.record("status", status.as_u16());
+        }
+        if let Some(observer) = &client.observer {
+            observer.0.on_request(&RequestEvent {
+                method: method.clone(),
+                url: url.clone(),
+                status,
+                elapsed: started.elapsed(),
+            });
+        }
+
+        outcome
+            .map_err(|err| match client.timeout {
+                Some(after) if err.is_timeout() => TimeoutError {
+                    url: err.url().map(|url| url.to_string()).unwrap_or_default(),
+                    after,
+                }
+                .into(),
+                _ => anyhow::Error::from(err),
+            })
+            .with_context(|| format!("{} {} failed to execute", method, url))
+    }
+
+    async fn send_retrying<T>(
+        client: &UserClient<T>,
+        request: Request,
+        retry: Option<&RetryPolicy>,
+    ) -> reqwest::Result<Response> {
+        let transport = client.transport();
+        let policy = match retry {
+            Some(policy) if policy.max_attempts > 1 => policy,
+            _ => return transport.execute(request).await,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let Some(current) = request.try_clone() else {
+                return transport.execute(request).await;
+            };
+            let outcome = transport.execute(current).await;
+            let retryable = match &outcome {
+                Ok(resp) => policy.should_retry_status(resp.status().as_u16()),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !retryable || attempt >= policy.max_attempts {
+                return outcome;
+            }
+            let retry_after = outcome.as_ref().ok().and_then(Self::retry_after);
+            tokio::time::sleep(policy.delay(attempt, retry_after)).await;
+            attempt += 1;
+        }
+    }
+
+    /// The `Retry-After` delay of a 429 response, when given in seconds.
+    pub(crate) fn retry_after(resp: &Response) -> Option<Duration> {
+        if resp.status() != StatusCode::TOO_MANY_REQUESTS {
+            return None;
+        }
+        let seconds = resp.headers().get(RETRY_AFTER)?.to_str().ok()?;
+        Some(Duration::from_secs(seconds.trim().parse().ok()?))
+    }
+
     pub async fn get<T>(
         client: &UserClient<T>,
         url: &str,
         query_params: Option<Vec<(&str, &str)>>,
     ) -> Result<Response> {
-        let http = ReqwestClient::new();
-        let mut request = http.get(url);
-        request = Self::attach_auth_info(request, client);
+        Self::get_with(client, url, query_params, None).await
+    }
+
+    /// GET with a per-request retry override; `None` uses the client's policy.
+    pub async fn get_with<T>(
+        client: &UserClient<T>,
+        url: &str,
+        query_params: Option<Vec<(&str, &str)>>,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let mut request = Self::request(client, Method::GET, url);
 
         if let Some(pairs) = query_params {
             request = request.query(&pairs);
         }
 
-        let resp = request.send().await?;
-        Ok(resp)
+        Self::send(client, request, retry.or(client.retry_policy.as_ref())).await
+    }
+
+    /// GET for long-lived streaming responses; the client's timeout does not apply.
+    pub async fn get_stream<T>(client: &UserClient<T>, url: &str) -> Result<Response> {
+        let request = Self::request(client, Method::GET, url);
+        let request = Self::with_default_headers(client, request)?.build()?;
+        client
+            .transport()
+            .execute(request)
+            .await
+            .with_context(|| format!("GET {} failed to execute", url))
     }
 
     pub async fn post<T>(
@@ -38,19 +238,42 @@ impl Httpc {
         url: &str,
         body_content: String,
     ) -> Result<Response> {
-        let http = ReqwestClient::new();
-        let mut request = http.post(url).header("Content-Type", "application/json");
-        request = Self::attach_auth_info(request, client);
-        let resp = request.body(body_content).send().await?;
-        Ok(resp)
+        Self::post_with(client, url, body_content, None).await
+    }
+
+    /// POST retried only when `retry` opts in; the client's policy never applies.
+    pub async fn post_with<T>(
+        client: &UserClient<T>,
+        url: &str,
+        body_content: String,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let request =
+            Self::request(client, Method::POST, url).header("Content-Type", "application/json");
+        Self::send(client, request.body(body_content), retry).await
+    }
+
+    pub async fn post_multipart<T>(
+        client: &UserClient<T>,
+        url: &str,
+        form: Form,
+    ) -> Result<Response> {
+        let request = Self::request(client, Method::POST, url);
+        Self::send(client, request.multipart(form), None).await
     }
 
     pub async fn delete<T>(client: &UserClient<T>, url: &str) -> Result<Response> {
-        let http = ReqwestClient::new();
-        let request = http.delete(url);
-        let request = Self::attach_auth_info(request, client);
-        let resp = request.send().await?;
-        Ok(resp)
+        Self::delete_with(client, url, None).await
+    }
+
+    /// DELETE with a per-request retry override; `None` uses the client's policy.
+    pub async fn delete_with<T>(
+        client: &UserClient<T>,
+        url: &str,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let request = Self::request(client, Method::DELETE, url);
+        Self::send(client, request, retry.or(client.retry_policy.as_ref())).await
     }
 
     pub async fn patch<T>(
@@ -58,10 +281,18 @@ impl Httpc {
         url: &str,
         body_content: String,
     ) -> Result<Response> {
-        let http = ReqwestClient::new();
-        let mut request = http.patch(url).header("Content-Type", "application/json");
-        request = Self::attach_auth_info(request, client);
-        let resp = request.body(body_content).send().await?;
-        Ok(resp)
+        Self::patch_with(client, url, body_content, None).await
+    }
+
+    /// PATCH retried only when `retry` opts in; the client's policy never applies.
+    pub async fn patch_with<T>(
+        client: &UserClient<T>,
+        url: &str,
+        body_content: String,
+        retry: Option<&RetryPolicy>,
+    ) -> Result<Response> {
+        let request =
+            Self::request(client, Method::PATCH, url).header("Content-Type", "application/json");
+        Self::send(client, request.body(body_content), retry).await
     }
 }
@@ -7,6 +7,15 @@ pub mod admin;
 /// Records Related Operations
 pub mod records;
 
+/// Transactional Batch Requests
+pub mod batch;
+
+/// Typed Filter Expressions
+pub mod filter;
+
+/// Typed Sort Expressions
+pub mod sort;
+
 /// Collections Related Operations
 pub mod collections;
 
@@ -16,8 +25,30 @@ pub mod logs;
 /// Settings Related Operations
 pub mod settings;
 
-/// Realtime Server [Not Available]
+/// Retry Policies
+pub mod retry;
+
+/// Request Instrumentation
+pub mod observer;
+
+/// Pluggable HTTP Transport
+pub mod transport;
+
+/// Reusable Record Model Helpers
+pub mod models;
+
+/// Local OAuth2 Redirect Handling
+#[cfg(feature = "oauth2-callback")]
+pub mod oauth2;
+
+/// Blocking Client for non-async code
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+/// Realtime Subscriptions
 pub mod rts;
 
+/// Error Types
+pub mod error;
+
 mod httpc;
-mod error;
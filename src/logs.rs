@@ -1,8 +1,13 @@
-use crate::client::{Client};
+use crate::client::{Client, SUPERUSERS_COLLECTION};
 use crate::httpc::Httpc;
+use crate::records::{
+    check_page_limit, has_next_page, is_last_page, stable_sort, unknown_total, DEFAULT_MAX_PAGES,
+};
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::de::{self, Deserializer};
 use serde::Deserialize;
+use serde_json::Value;
 use std::collections::HashMap;
 
 pub struct LogsManager<'a, A> {
@@ -14,8 +19,11 @@ pub struct LogListRequestBuilder<'a, A> {
     pub client: &'a Client<A>,
     pub page: i32,
     pub per_page: i32,
-    pub sort: Option<&'a str>,
+    pub sort: Option<String>,
     pub filter: Option<&'a str>,
+    pub skip_total: bool,
+    /// Extra query parameters; see [`query_param`](Self::query_param).
+    pub extra_params: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -42,7 +50,48 @@ pub struct LogListItem {
     pub ip: Option<String>,
     pub referer: String,
     pub user_agent: String,
-    pub meta: HashMap<String, String>,
+    /// Request context such as `auth`, `authId` or nested error details.
+    #[serde(default)]
+    pub meta: HashMap<String, Value>,
+}
+
+/// Who made a logged request, from the `auth` and `authId` meta fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogAuthContext {
+    /// The request carried no auth token.
+    Guest,
+    /// A superuser (admin) made the request.
+    Admin { id: Option<String> },
+    /// An auth record made the request. `collection` is `None` when the log
+    /// only records that it was a user or an auth record.
+    User {
+        collection: Option<String>,
+        id: Option<String>,
+    },
+}
+
+impl LogListItem {
+    /// Who made the request; logs without auth meta are treated as guests.
+    pub fn auth(&self) -> LogAuthContext {
+        let id = self
+            .meta
+            .get("authId")
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string);
+        match self.meta.get("auth").and_then(Value::as_str) {
+            None | Some("") | Some("guest") => LogAuthContext::Guest,
+            Some("admin") | Some(SUPERUSERS_COLLECTION) => LogAuthContext::Admin { id },
+            Some("user") | Some("authRecord") => LogAuthContext::User {
+                collection: None,
+                id,
+            },
+            Some(collection) => LogAuthContext::User {
+                collection: Some(collection.to_string()),
+                id,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -50,14 +99,41 @@ pub struct LogListItem {
 pub struct LogList {
     pub page: i32,
     pub per_page: i32,
+    /// `-1` when the server did not count the logs, as with `skip_total`
+    /// or list responses that omit `totalItems`.
+    #[serde(default = "unknown_total")]
     pub total_items: i32,
+    /// `-1` when the server did not count the logs.
+    #[serde(default = "unknown_total")]
+    pub total_pages: i32,
     pub items: Vec<LogListItem>,
 }
 
+impl LogList {
+    /// Whether another page can be requested.
+    pub fn has_next_page(&self) -> bool {
+        has_next_page(self.page, self.per_page, self.items.len(), self.total_pages)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct LogStatDataPoint {
     pub total: i32,
-    pub date: String,
+    /// The start of the hour the requests were counted in.
+    #[serde(deserialize_with = "deserialize_stat_date")]
+    pub date: DateTime<Utc>,
+}
+
+/// Parse a stats date such as `2022-06-01 19:00:00.000Z`. PocketBase writes
+/// these in UTC, with or without the trailing `Z`.
+fn deserialize_stat_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    let date = String::deserialize(deserializer)?;
+    let naive = date.trim_end_matches('Z').replacen('T', " ", 1);
+    NaiveDateTime::parse_from_str(&naive, "%Y-%m-%d %H:%M:%S%.f")
+        .map(|naive| naive.and_utc())
+        .map_err(|e| de::Error::custom(format!("invalid log stats date `{}`: {}", date, e)))
 }
 
 impl<'a, A: Clone> LogStatisticsRequestBuilder<'a, A> {
@@ -69,7 +145,7 @@ impl<'a, A: Clone> LogStatisticsRequestBuilder<'a, A> {
     }
 
     pub async fn call(&self) -> Result<Vec<LogStatDataPoint>> {
-        let url = format!("{}/api/logs/requests/stats", self.client.base_url);
+        let url = self.client.endpoint("logs/requests/stats");
         let mut build_opts = Vec::new();
         if let Some(filter_opts) = &self.filter {
             build_opts.push(("filter", filter_opts.to_owned()));
@@ -87,7 +163,7 @@ impl<'a, A: Clone> LogStatisticsRequestBuilder<'a, A> {
 
 impl<'a, A> LogViewRequestBuilder<'a, A> {
     pub async fn call(&self) -> Result<LogListItem> {
-        let url = format!("{}/api/logs/requests/{}", self.client.base_url, self.id);
+        let url = self.client.endpoint(&format!("logs/requests/{}", self.id));
         match Httpc::get(self.client, &url, None).await {
             Ok(result) => {
                 let response = result.json::<LogListItem>().await?;
@@ -120,19 +196,39 @@ impl<'a, A: Clone> LogListRequestBuilder<'a, A> {
         }
     }
 
-    pub fn sort(&self, sort_opts: &'a str) -> Self {
+    /// Accepts a raw sort string or a [`Sort`](crate::sort::Sort).
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
+        LogListRequestBuilder {
+            sort: Some(sort_opts.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Append an extra `key=value` query parameter, sent after the typed
+    /// ones, for parameters this builder doesn't model.
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        let mut extra_params = self.extra_params.clone();
+        extra_params.push((key.to_string(), value.into()));
         LogListRequestBuilder {
-            sort: Some(sort_opts),
+            extra_params,
+            ..self.clone()
+        }
+    }
+
+    /// Skip counting the matching logs; `total_items` is then not meaningful.
+    pub fn skip_total(&self, skip_total: bool) -> Self {
+        LogListRequestBuilder {
+            skip_total,
             ..self.clone()
         }
     }
 
     pub async fn call(&self) -> Result<LogList> {
-        let url = format!("{}/api/logs/requests", self.client.base_url);
+        let url = self.client.endpoint("logs/requests");
         let mut build_opts = Vec::new();
 
         if let Some(sort_opts) = &self.sort {
-            build_opts.push(("sort", sort_opts.to_owned()))
+            build_opts.push(("sort", sort_opts.as_str()))
         }
         if let Some(filter_opts) = &self.filter {
             build_opts.push(("filter", filter_opts.to_owned()))
@@ -141,6 +237,12 @@ impl<'a, A: Clone> LogListRequestBuilder<'a, A> {
         let page_opts = self.page.to_string();
         build_opts.push(("perPage", per_page_opts.as_str()));
         build_opts.push(("page", page_opts.as_str()));
+        if self.skip_total {
+            build_opts.push(("skipTotal", "1"));
+        }
+        for (key, value) in &self.extra_params {
+            build_opts.push((key, value));
+        }
 
         match Httpc::get(self.client, &url, Some(build_opts)).await {
             Ok(result) => {
@@ -153,6 +255,43 @@ impl<'a, A: Clone> LogListRequestBuilder<'a, A> {
 }
 
 impl<'a, A: Clone> LogsManager<'a, A> {
+    /// Fetch every request log matching `filter`, paging until the listing
+    /// ends. `id` is appended to `sort` so logs sharing a timestamp are
+    /// neither repeated nor skipped across pages.
+    pub async fn get_all(
+        &self,
+        filter: Option<&str>,
+        sort: Option<&str>,
+    ) -> Result<Vec<LogListItem>> {
+        let per_page = self.client.per_page_limit();
+        let mut list = self.list().sort(stable_sort(sort)).per_page(per_page);
+        if let Some(filter) = filter {
+            list = list.filter(filter);
+        }
+
+        let mut all_items = Vec::new();
+        let mut page = 1;
+        loop {
+            let page_resp = list.page(page).call().await?;
+            let last_page = is_last_page(
+                page_resp.page,
+                page_resp.items.len(),
+                page_resp.total_items,
+                all_items.len() + page_resp.items.len(),
+                per_page,
+            );
+            all_items.extend(page_resp.items);
+
+            if last_page {
+                break;
+            }
+            check_page_limit(page, DEFAULT_MAX_PAGES, all_items.len())?;
+            page += 1;
+        }
+
+        Ok(all_items)
+    }
+
     pub fn list(&self) -> LogListRequestBuilder<'a, A> {
         LogListRequestBuilder {
             client: self.client,
@@ -160,6 +299,8 @@ impl<'a, A: Clone> LogsManager<'a, A> {
             per_page: 100,
             sort: None,
             filter: None,
+            skip_total: false,
+            extra_params: Vec::new(),
         }
     }
 
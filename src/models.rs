@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The system fields PocketBase returns on every record, to embed in record
+/// structs with `#[serde(flatten)]`:
+///
+/// ```
+/// use pocketbase_client::models::BaseModel;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Default, Serialize, Deserialize)]
+/// struct Post {
+///     #[serde(flatten)]
+///     base: BaseModel,
+///     title: String,
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseModel {
+    pub id: String,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    #[serde(default)]
+    pub collection_id: String,
+    #[serde(default)]
+    pub collection_name: String,
+}
+
+/// The fields PocketBase adds to records of auth collections such as
+/// `users`, to embed like [`BaseModel`]:
+///
+/// ```
+/// use pocketbase_client::models::AuthRecord;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Default, Serialize, Deserialize)]
+/// struct User {
+///     #[serde(flatten)]
+///     auth: AuthRecord,
+///     name: String,
+/// }
+/// ```
+///
+/// `email` is empty when it is hidden from the requester by
+/// `emailVisibility`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthRecord {
+    #[serde(flatten)]
+    pub base: BaseModel,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub email_visibility: bool,
+    #[serde(default)]
+    pub verified: bool,
+}
+
+/// A record of type `T` together with the relations PocketBase expanded into
+/// its `expand` object, decoded into `E`.
+///
+/// Each field of `E` is named after a relation field passed to `expand`: an
+/// `Option<R>` for single relations and a `Vec<R>` for multiple ones. Nested
+/// expands such as `author.team` nest another `Expanded` inside `E`.
+/// `expand` is `None` when the response has no `expand` object, which usually
+/// means the request didn't ask for one:
+///
+/// ```
+/// use pocketbase_client::models::Expanded;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Post {
+///     title: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct PostExpand {
+///     author: Option<User>,
+///     #[serde(default)]
+///     tags: Vec<serde_json::Value>,
+/// }
+///
+/// // client.records("posts").view(id).expand("author,tags").call::<Expanded<Post, PostExpand>>()
+/// let post: Expanded<Post, PostExpand> = serde_json::from_str(
+///     r#"{"title": "hello", "expand": {"author": {"name": "ana"}}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(post.record.title, "hello");
+/// assert_eq!(post.expand.unwrap().author.unwrap().name, "ana");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expanded<T, E> {
+    #[serde(flatten)]
+    pub record: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<E>,
+}
+
+impl<T: Default, E> Default for Expanded<T, E> {
+    fn default() -> Self {
+        Expanded {
+            record: T::default(),
+            expand: None,
+        }
+    }
+}
+
+/// A field that is only ever sent to PocketBase, never read back.
+///
+/// PocketBase does not return write-only fields such as an auth record's
+/// `password`, so modelling them as a plain `String` means a fetched record
+/// holds an empty value that a naive read-modify-write would send back.
+/// `WriteOnly` ignores whatever the server returns and is left unset after
+/// deserialization; pair it with `skip_serializing_if` so an unset value is
+/// omitted from create/update payloads:
+///
+/// ```
+/// use pocketbase_client::models::WriteOnly;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct User {
+///     email: String,
+///     #[serde(default, skip_serializing_if = "WriteOnly::is_unset")]
+///     password: WriteOnly<String>,
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOnly<T>(Option<T>);
+
+impl<T> WriteOnly<T> {
+    pub fn new(value: T) -> Self {
+        WriteOnly(Some(value))
+    }
+
+    pub fn unset() -> Self {
+        WriteOnly(None)
+    }
+
+    pub fn is_unset(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+}
+
+impl<T> Default for WriteOnly<T> {
+    fn default() -> Self {
+        WriteOnly::unset()
+    }
+}
+
+impl<T> From<T> for WriteOnly<T> {
+    fn from(value: T) -> Self {
+        WriteOnly::new(value)
+    }
+}
+
+impl<T: Serialize> Serialize for WriteOnly<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for WriteOnly<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IgnoredAny::deserialize(deserializer)?;
+        Ok(WriteOnly::unset())
+    }
+}
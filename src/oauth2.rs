@@ -0,0 +1,167 @@
+use crate::client::{Auth, AuthError, AuthProviderInfo, Client, NoAuth};
+use anyhow::{anyhow, Result};
+use reqwest::Url;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use url::form_urlencoded::byte_serialize;
+
+const CALLBACK_PATH: &str = "/callback";
+/// Requests with a longer request line and headers are rejected unread.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+const CLOSE_PAGE: &str = "<html><body>Login complete. You can close this window.</body></html>";
+
+/// A temporary local HTTP server that catches the OAuth2 provider's redirect,
+/// so CLI and desktop apps can log in without copying the code by hand.
+///
+/// Open [`OAuth2Callback::auth_url`] in a browser, then pass the callback to
+/// [`Client::auth_with_oauth2_callback`] to wait for the redirect.
+pub struct OAuth2Callback {
+    listener: TcpListener,
+    redirect_url: String,
+}
+
+impl OAuth2Callback {
+    /// Listen on `addr`, e.g. `127.0.0.1:0` for any free port. The redirect
+    /// URL must be allowed by the provider's app settings.
+    pub async fn bind(addr: &str) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let redirect_url = format!("http://{}{}", listener.local_addr()?, CALLBACK_PATH);
+        Ok(OAuth2Callback {
+            listener,
+            redirect_url,
+        })
+    }
+
+    /// The URL the provider redirects back to.
+    pub fn redirect_url(&self) -> &str {
+        &self.redirect_url
+    }
+
+    /// The provider's login page, redirecting back to this callback.
+    pub fn auth_url(&self, provider: &AuthProviderInfo) -> String {
+        let redirect_url: String = byte_serialize(self.redirect_url.as_bytes()).collect();
+        format!("{}{}", provider.auth_url, redirect_url)
+    }
+
+    /// Wait for the redirect and return its `code` after checking `state`.
+    async fn code(&self, state: &str) -> Result<String> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+            let query = match read_callback_request(&mut stream).await? {
+                CallbackRequest::Query(query) => query,
+                CallbackRequest::Other => {
+                    respond(&mut stream, "404 Not Found", "text/plain", "").await;
+                    continue;
+                }
+                CallbackRequest::TooLarge => {
+                    respond(
+                        &mut stream,
+                        "431 Request Header Fields Too Large",
+                        "text/plain",
+                        "",
+                    )
+                    .await;
+                    continue;
+                }
+            };
+
+            let param = |name: &str| {
+                query
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+            };
+            let result = if let Some(error) = param("error") {
+                Err(anyhow!("OAuth2 login failed: {}", error))
+            } else if param("state").as_deref() != Some(state) {
+                Err(anyhow!("OAuth2 redirect state does not match"))
+            } else {
+                param("code").ok_or_else(|| anyhow!("OAuth2 redirect without a code"))
+            };
+
+            match &result {
+                Ok(_) => respond(&mut stream, "200 OK", "text/html", CLOSE_PAGE).await,
+                // Plain text, as the message quotes the redirect's query.
+                Err(e) => {
+                    respond(&mut stream, "400 Bad Request", "text/plain", &e.to_string()).await
+                }
+            }
+            return result;
+        }
+    }
+}
+
+impl Client<NoAuth> {
+    /// Wait up to `timeout` for `callback` to receive the provider's redirect,
+    /// then complete the login with [`Client::auth_with_oauth2`].
+    pub async fn auth_with_oauth2_callback(
+        &self,
+        collection: &str,
+        provider: &AuthProviderInfo,
+        callback: OAuth2Callback,
+        timeout: Duration,
+    ) -> Result<Client<Auth>, AuthError> {
+        let code = tokio::time::timeout(timeout, callback.code(&provider.state))
+            .await
+            .map_err(|_| AuthError::Other("timed out waiting for the OAuth2 redirect".into()))??;
+
+        self.auth_with_oauth2(
+            collection,
+            &provider.name,
+            &code,
+            &provider.code_verifier,
+            callback.redirect_url(),
+        )
+        .await
+    }
+}
+
+enum CallbackRequest {
+    /// The query of a `GET` to the callback path.
+    Query(Vec<(String, String)>),
+    /// Any other request.
+    Other,
+    /// A request head longer than [`MAX_REQUEST_HEAD`].
+    TooLarge,
+}
+
+async fn read_callback_request(stream: &mut TcpStream) -> Result<CallbackRequest> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        if request.len() > MAX_REQUEST_HEAD {
+            return Ok(CallbackRequest::TooLarge);
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+    let (Some("GET"), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(CallbackRequest::Other);
+    };
+    let url = Url::parse(&format!("http://localhost{}", target))?;
+    if url.path() != CALLBACK_PATH {
+        return Ok(CallbackRequest::Other);
+    }
+    Ok(CallbackRequest::Query(
+        url.query_pairs().into_owned().collect(),
+    ))
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\ncontent-type: {}; charset=utf-8\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    // The browser may already be gone; the login outcome doesn't depend on it.
+    let _ = stream.write_all(response.as_bytes()).await;
+}
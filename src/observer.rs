@@ -0,0 +1,44 @@
+use reqwest::{Method, StatusCode};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A finished HTTP request, as passed to a [`RequestObserver`].
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: Method,
+    pub url: String,
+    /// `None` when no response was received, e.g. on a connection error or
+    /// timeout.
+    pub status: Option<StatusCode>,
+    /// Time until the response headers arrived, including retries.
+    pub elapsed: Duration,
+}
+
+/// Called after every request a [`Client`](crate::client::Client) sends,
+/// e.g. to log or time them. Set it with
+/// [`Client::with_request_observer`](crate::client::Client::with_request_observer).
+///
+/// Closures taking a `&RequestEvent` implement this trait.
+pub trait RequestObserver: Send + Sync {
+    fn on_request(&self, event: &RequestEvent);
+}
+
+impl<F> RequestObserver for F
+where
+    F: Fn(&RequestEvent) + Send + Sync,
+{
+    fn on_request(&self, event: &RequestEvent) {
+        self(event)
+    }
+}
+
+/// A shared [`RequestObserver`], so clients stay `Clone` and `Debug`.
+#[derive(Clone)]
+pub(crate) struct Observer(pub(crate) Arc<dyn RequestObserver>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestObserver")
+    }
+}
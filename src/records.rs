@@ -1,10 +1,33 @@
-use crate::client::Client;
+use crate::client::{Client, ErrorResponse, ResponseMeta, WriteAccess};
+use crate::collections::Collection;
+#[cfg(feature = "cancellation")]
+use crate::error::CancelledError;
+use crate::error::{
+    BodyTooLargeError, ItemError, PageLimitError, RateLimitedError, RecordListError,
+    RecordViewError, RecordWriteError,
+};
+use crate::filter::Filter;
 use crate::httpc::Httpc;
-use crate::error::RecordViewError;
+use crate::retry::RetryPolicy;
 use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, Response, StatusCode};
 use serde::Serialize;
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, de::IgnoredAny, Deserialize};
+use serde_json::{Map, Value};
 use std::cmp;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use tokio_util::io::{ReaderStream, StreamReader, SyncIoBridge};
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
+
+/// Upper bound on the pages `get_all` fetches unless overridden with
+/// [`RecordsListRequestBuilder::max_pages`]; reaching it without reaching
+/// the last page fails with a [`PageLimitError`].
+pub const DEFAULT_MAX_PAGES: i32 = 1000;
 
 #[derive(Debug, Clone)]
 pub struct RecordsManager<'a, A> {
@@ -19,8 +42,34 @@ pub struct RecordsListRequestBuilder<'a, A> {
     pub filter: Option<String>,
     pub sort: Option<String>,
     pub expand: Option<String>,
+    pub fields: Option<String>,
     pub page: i32,
     pub per_page: i32,
+    pub retry: Option<RetryPolicy>,
+    pub max_pages: i32,
+    pub skip_total: bool,
+    /// Extra query parameters; see [`query_param`](Self::query_param).
+    pub extra_params: Vec<(String, String)>,
+    /// Cancels older pending requests; see [`request_key`](Self::request_key).
+    pub request_key: Option<String>,
+    /// Stops paging when cancelled; see [`cancel_on`](Self::cancel_on).
+    #[cfg(feature = "cancellation")]
+    pub cancel: Option<CancellationToken>,
+}
+
+/// The filter/sort/expand/fields settings of a list request, serializable so
+/// a saved view can be stored and re-applied with
+/// [`RecordsListRequestBuilder::with_params`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expand: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -28,136 +77,550 @@ pub struct RecordsListRequestBuilder<'a, A> {
 pub struct RecordList<T> {
     pub page: i32,
     pub per_page: i32,
+    /// `-1` when the server did not count the items, as with `skip_total`
+    /// or list responses that omit `totalItems`.
+    #[serde(default = "unknown_total")]
     pub total_items: i32,
+    /// `-1` when the server did not count the items.
+    #[serde(default = "unknown_total")]
+    pub total_pages: i32,
     pub items: Vec<T>,
 }
 
+pub(crate) fn unknown_total() -> i32 {
+    -1
+}
+
+/// Whether a page after `page` exists. Without a page count (`total_pages <
+/// 0`), a full page is taken to mean there may be more.
+pub(crate) fn has_next_page(page: i32, per_page: i32, items: usize, total_pages: i32) -> bool {
+    if total_pages < 0 {
+        return items > 0 && items >= per_page as usize;
+    }
+    page < total_pages
+}
+
+impl<T> RecordList<T> {
+    /// Whether another page can be requested, e.g. to enable a "next" button.
+    pub fn has_next_page(&self) -> bool {
+        has_next_page(self.page, self.per_page, self.items.len(), self.total_pages)
+    }
+
+    fn is_last_page(&self, fetched: usize, per_page: i32, skip_total: bool) -> bool {
+        let total_items = if skip_total { -1 } else { self.total_items };
+        is_last_page(self.page, self.items.len(), total_items, fetched, per_page)
+    }
+}
+
+/// Whether paging can stop once `fetched` items, this page's `items`
+/// included, were collected. A short or empty page, or reaching the server's
+/// current page count, ends the listing even if `total_items` changed between
+/// requests. When the server reports no count (`total_items < 0`), only a
+/// short page ends it.
+pub(crate) fn is_last_page(
+    page: i32,
+    items: usize,
+    total_items: i32,
+    fetched: usize,
+    per_page: i32,
+) -> bool {
+    if items < per_page as usize {
+        return true;
+    }
+    if total_items < 0 {
+        return false;
+    }
+    let total_items = total_items as usize;
+    fetched >= total_items || page as i64 * per_page as i64 >= total_items as i64
+}
+
+/// `sort` with `id` appended as a tiebreaker, so offset pagination neither
+/// repeats nor skips items sharing sort keys.
+pub(crate) fn stable_sort(sort: Option<&str>) -> String {
+    match sort {
+        None | Some("") => "id".to_string(),
+        Some(sort) => {
+            let has_id = sort
+                .split(',')
+                .map(|term| term.trim().trim_start_matches(['-', '+']))
+                .any(|field| field == "id");
+            if has_id {
+                sort.to_string()
+            } else {
+                format!("{},id", sort)
+            }
+        }
+    }
+}
+
+/// The start of `body` for error messages: up to 2000 bytes, cut back to a
+/// character boundary.
+pub(crate) fn body_snippet(body: &str) -> &str {
+    let mut end = cmp::min(2000, body.len());
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    &body[..end]
+}
+
+/// Reads through to `inner`, keeping the first bytes for error messages and
+/// failing once more than `limit` bytes were read.
+struct SnippetReader<R> {
+    inner: R,
+    snippet: Vec<u8>,
+    read: usize,
+    limit: Option<usize>,
+}
+
+impl<R> SnippetReader<R> {
+    fn exceeded(&self) -> bool {
+        self.limit.is_some_and(|limit| self.read > limit)
+    }
+}
+
+impl<R: Read> Read for SnippetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let keep = cmp::min(2000 - self.snippet.len(), read);
+        self.snippet.extend_from_slice(&buf[..keep]);
+        self.read += read;
+        if self.exceeded() {
+            return Err(io::Error::other("response body too large"));
+        }
+        Ok(read)
+    }
+}
+
+/// Read the body of `resp` as text, failing with a [`BodyTooLargeError`]
+/// once it grows past the client's `max_body_size`.
+async fn read_body<A>(client: &Client<A>, resp: Response, url: &str) -> Result<String> {
+    let Some(limit) = client.max_body_size else {
+        return resp
+            .text()
+            .await
+            .with_context(|| format!("Reading response body from {} failed", url));
+    };
+    let too_large = || BodyTooLargeError {
+        url: url.to_string(),
+        limit,
+    };
+    if resp.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(too_large().into());
+    }
+
+    let mut body = Vec::new();
+    let mut chunks = resp.bytes_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.with_context(|| format!("Reading response body from {} failed", url))?;
+        if body.len() + chunk.len() > limit {
+            return Err(too_large().into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    String::from_utf8(body).with_context(|| format!("Response body from {} is not UTF-8", url))
+}
+
+/// Deserialize the body of `resp` from its byte stream on a blocking thread,
+/// reading at most the client's `max_body_size`.
+async fn decode_streaming<A, T: DeserializeOwned + Send + 'static>(
+    client: &Client<A>,
+    resp: Response,
+    url: &str,
+) -> Result<T> {
+    let limit = client.max_body_size;
+    if let Some(limit) = limit.filter(|&limit| resp.content_length() > Some(limit as u64)) {
+        return Err(BodyTooLargeError {
+            url: url.to_string(),
+            limit,
+        }
+        .into());
+    }
+    let body = StreamReader::new(
+        resp.bytes_stream()
+            .map(|chunk| chunk.map_err(io::Error::other)),
+    );
+    let mut reader = BufReader::new(SnippetReader {
+        inner: SyncIoBridge::new(body),
+        snippet: Vec::new(),
+        read: 0,
+        limit,
+    });
+
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut reader);
+        serde_path_to_error::deserialize::<_, T>(&mut deserializer).map_err(|de_err| {
+            if let (true, Some(limit)) = (reader.get_ref().exceeded(), limit) {
+                return BodyTooLargeError { url, limit }.into();
+            }
+            anyhow!(
+                "JSON decode error at path `{}`: {}\nResponse (truncated):\n{}",
+                de_err.path(),
+                de_err,
+                String::from_utf8_lossy(&reader.get_ref().snippet)
+            )
+        })
+    })
+    .await?
+}
+
+/// A [`RateLimitedError`] when `resp` is a 429.
+fn rate_limited(resp: &Response, url: &str) -> Result<()> {
+    if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(RateLimitedError {
+            url: url.to_string(),
+            retry_after: Httpc::retry_after(resp),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// A [`PageLimitError`] when `page` is the last one `max_pages` allows;
+/// only called while more pages remain.
+pub(crate) fn check_page_limit(page: i32, max_pages: i32, fetched: usize) -> Result<()> {
+    if page >= max_pages {
+        return Err(PageLimitError { max_pages, fetched }.into());
+    }
+    Ok(())
+}
+
 impl<'a, A: Clone> RecordsListRequestBuilder<'a, A> {
-    pub async fn call<T: Default + DeserializeOwned>(&self) -> Result<RecordList<T>> {
-        let url = format!(
-            "{}/api/collections/{}/records",
-            self.client.base_url, self.collection_name
-        );
+    fn url(&self) -> String {
+        self.client
+            .endpoint(&format!("collections/{}/records", self.collection_name))
+    }
 
-        let mut build_opts: Vec<(&str, &str)> = vec![];
+    /// This builder with `id` appended to the sort as a tiebreaker.
+    fn stable_sort(&self) -> Self {
+        Self {
+            sort: Some(stable_sort(self.sort.as_deref())),
+            ..self.clone()
+        }
+    }
+
+    /// `per_page` clamped to the client's `max_per_page`, so the paging math
+    /// matches the page size the server actually uses.
+    fn effective_per_page(&self) -> i32 {
+        self.per_page.clamp(1, self.client.per_page_limit())
+    }
+
+    fn query_params(&self) -> Vec<(&str, String)> {
+        let mut build_opts = vec![];
         if let Some(filter_opts) = &self.filter {
-            build_opts.push(("filter", filter_opts))
+            build_opts.push(("filter", filter_opts.clone()))
         }
         if let Some(sort_opts) = &self.sort {
-            build_opts.push(("sort", sort_opts))
+            build_opts.push(("sort", sort_opts.clone()))
         }
-        if let Some(expand_opts) = &self.expand {
-            build_opts.push(("expand", expand_opts))
+        if let Some(expand_opts) = self.expand.as_deref().filter(|e| !e.is_empty()) {
+            build_opts.push(("expand", expand_opts.to_string()))
         }
-        let per_page_opts = self.per_page.to_string();
-        let page_opts = self.page.to_string();
-        build_opts.push(("perPage", per_page_opts.as_str()));
-        build_opts.push(("page", page_opts.as_str()));
-        let resp = Httpc::get(self.client, &url, Some(build_opts))
-            .await
-            .with_context(|| format!("GET {} failed to execute", url))?;
+        if let Some(fields_opts) = &self.fields {
+            build_opts.push(("fields", fields_opts.clone()))
+        }
+        build_opts.push(("perPage", self.per_page.to_string()));
+        build_opts.push(("page", self.page.to_string()));
+        if self.skip_total {
+            build_opts.push(("skipTotal", "1".to_string()));
+        }
+        for (key, value) in &self.extra_params {
+            build_opts.push((key.as_str(), value.clone()));
+        }
+        build_opts
+    }
 
-        let status = resp.status();
-        let body = resp
-            .text()
+    /// Send the list request and hand back the raw response without reading
+    /// its body, for custom streaming or header inspection.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.url();
+        let build_opts = self.query_params();
+        let query = build_opts.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        Httpc::get_with(self.client, &url, Some(query), self.retry.as_ref()).await
+    }
+
+    pub async fn call<T: Default + DeserializeOwned>(
+        &self,
+    ) -> Result<RecordList<T>, RecordListError> {
+        self.call_with_meta().await.map(|(_, list)| list)
+    }
+
+    /// Fetch one page while paging, after `fetched` items. With a
+    /// cancellation token set, an in-flight or not yet started request is
+    /// abandoned once the token is cancelled.
+    #[cfg_attr(not(feature = "cancellation"), allow(unused_variables))]
+    async fn call_page<T: Default + DeserializeOwned>(
+        &self,
+        fetched: usize,
+    ) -> Result<RecordList<T>> {
+        #[cfg(feature = "cancellation")]
+        if let Some(token) = &self.cancel {
+            return match token.run_until_cancelled(self.call::<T>()).await {
+                Some(page) => Ok(page?),
+                None => Err(CancelledError { fetched }.into()),
+            };
+        }
+        Ok(self.call::<T>().await?)
+    }
+
+    /// Like [`call`](Self::call), but decode the page while it is received
+    /// instead of buffering the whole body first, bounding peak memory on very
+    /// large pages at some cost in speed. Decode errors only show the body up
+    /// to where decoding failed.
+    pub async fn call_streaming<T>(&self) -> Result<RecordList<T>>
+    where
+        T: Default + DeserializeOwned + Send + 'static,
+    {
+        self.client
+            .run_keyed(self.request_key.as_deref(), self.fetch_streaming())
             .await
-            .with_context(|| format!("Reading response body from {} failed", url))?;
+    }
+
+    async fn fetch_streaming<T>(&self) -> Result<RecordList<T>>
+    where
+        T: Default + DeserializeOwned + Send + 'static,
+    {
+        let url = self.url();
+        let resp = self.send_raw().await?;
+        rate_limited(&resp, &url)?;
 
+        let status = resp.status();
         if !status.is_success() {
-            let snippet_len = cmp::min(2000, body.len());
-            let snippet = &body[..snippet_len];
+            let body = read_body(self.client, resp, &url).await?;
             return Err(anyhow!(
                 "Request to {} failed: HTTP {}.\nResponse (truncated):\n{}",
                 url,
                 status.as_u16(),
-                snippet
+                body_snippet(&body)
             ));
         }
 
+        decode_streaming(self.client, resp, &url).await
+    }
+
+    /// Like [`call`](Self::call), also returning the response status and
+    /// headers, e.g. to inspect rate limits.
+    pub async fn call_with_meta<T: Default + DeserializeOwned>(
+        &self,
+    ) -> Result<(ResponseMeta, RecordList<T>), RecordListError> {
+        self.client
+            .run_keyed(self.request_key.as_deref(), self.fetch_with_meta())
+            .await
+    }
+
+    async fn fetch_with_meta<T: Default + DeserializeOwned>(
+        &self,
+    ) -> Result<(ResponseMeta, RecordList<T>), RecordListError> {
+        let url = self.url();
+        let resp = self.send_raw().await?;
+        let meta = ResponseMeta::of(&resp);
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(RecordListError::RateLimited {
+                retry_after: Httpc::retry_after(&resp),
+            });
+        }
+
+        let status = resp.status();
+        let body = read_body(self.client, resp, &url).await?;
+
+        if !status.is_success() {
+            return Err(RecordListError::Http {
+                status: status.as_u16(),
+                url,
+                body_snippet: body_snippet(&body).to_string(),
+            });
+        }
+
         let mut deserializer = serde_json::Deserializer::from_str(&body);
         match serde_path_to_error::deserialize::<_, RecordList<T>>(&mut deserializer) {
-            Ok(parsed) => Ok(parsed),
+            Ok(parsed) => Ok((meta, parsed)),
             Err(de_err) => {
-                let path = de_err.path().to_string();
                 // Show a short snippet to help diagnose server-side data issues
-                let snippet_len = cmp::min(2000, body.len());
-                let snippet = &body[..snippet_len];
-
-                Err(anyhow!(
-                    "JSON decode error at path `{}`: {}\nResponse (truncated):\n{}",
-                    path,
-                    de_err,
-                    snippet
-                ))
+                Err(RecordListError::Decode {
+                    path: de_err.path().to_string(),
+                    source: de_err,
+                    body_snippet: body_snippet(&body).to_string(),
+                })
             }
         }
     }
 
     pub async fn get_all<T>(&self) -> Result<Vec<T>>
     where
-        A: Clone,
+        T: Default + DeserializeOwned,
+    {
+        self.get_all_with_progress(|_, _| {}).await
+    }
+
+    /// Like [`get_all`](Self::get_all), calling `progress` after each page
+    /// with the number of items fetched so far and the server's `total_items`
+    /// (`-1` when not counted), e.g. to drive a progress bar.
+    pub async fn get_all_with_progress<T>(
+        &self,
+        mut progress: impl FnMut(usize, i32),
+    ) -> Result<Vec<T>>
+    where
         T: Default + DeserializeOwned,
     {
         let mut all_items = Vec::new();
         let mut page = 1;
-        let per_page = 1000;
-        let url = format!(
-            "{}/api/collections/{}/records",
-            self.client.base_url, self.collection_name
-        );
+        let per_page = self.client.per_page_limit();
+        let list = self.stable_sort();
 
         loop {
-            let mut build_opts: Vec<(&str, &str)> = vec![];
-            if let Some(filter_opts) = &self.filter {
-                build_opts.push(("filter", filter_opts))
-            }
-            if let Some(sort_opts) = &self.sort {
-                build_opts.push(("sort", sort_opts))
-            }
-            if let Some(expand_opts) = &self.expand {
-                build_opts.push(("expand", expand_opts))
+            let page_resp = list
+                .page(page)
+                .per_page(per_page)
+                .call_page::<T>(all_items.len())
+                .await?;
+
+            let last_page = page_resp.is_last_page(
+                all_items.len() + page_resp.items.len(),
+                per_page,
+                self.skip_total,
+            );
+            all_items.extend(page_resp.items);
+            progress(all_items.len(), page_resp.total_items);
+
+            if last_page {
+                break;
             }
-            let per_page_opts = &per_page.to_string();
-            let page_opts = &page.to_string();
+            check_page_limit(page, self.max_pages, all_items.len())?;
 
-            build_opts.push(("perPage", per_page_opts));
-            build_opts.push(("page", page_opts));
-            let result = Httpc::get(self.client, &url, Some(build_opts)).await;
+            page += 1;
+        }
 
-            let page_resp = match result {
-                Ok(result) => {
-                    let response = result.json::<RecordList<T>>().await?;
-                    Ok(response)
-                }
-                Err(e) => Err(e),
-            }?;
+        Ok(all_items)
+    }
+
+    /// Like [`get_all`](Self::get_all), but an item that fails to decode into
+    /// `T` is reported in the returned `ItemError`s instead of failing the
+    /// whole fetch.
+    pub async fn get_all_lenient<T>(&self) -> Result<(Vec<T>, Vec<ItemError>)>
+    where
+        T: DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        let mut fetched = 0;
+        let mut page = 1;
+        let per_page = self.client.per_page_limit();
+        let list = self.stable_sort();
+
+        loop {
+            let page_resp = list
+                .page(page)
+                .per_page(per_page)
+                .call_page::<Value>(fetched)
+                .await?;
+            let last_page =
+                page_resp.is_last_page(fetched + page_resp.items.len(), per_page, self.skip_total);
 
-            all_items.extend(page_resp.items.into_iter());
+            for raw in page_resp.items {
+                match serde_json::from_value::<T>(raw.clone()) {
+                    Ok(item) => items.push(item),
+                    Err(source) => errors.push(ItemError {
+                        index: fetched,
+                        id: raw.get("id").and_then(Value::as_str).map(str::to_string),
+                        raw,
+                        source,
+                    }),
+                }
+                fetched += 1;
+            }
 
-            if all_items.len() == page_resp.total_items as usize {
+            if last_page {
                 break;
             }
+            check_page_limit(page, self.max_pages, fetched)?;
 
             page += 1;
         }
 
-        Ok(all_items)
+        Ok((items, errors))
+    }
+
+    /// Lazily page through the matching records. The next page is only
+    /// requested once every item of the current one has been consumed.
+    pub fn stream<T>(&self) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: Default + DeserializeOwned + 'a,
+    {
+        let list = self.stable_sort().per_page(self.effective_per_page());
+        stream::unfold(Some(Ok(list)), |builder| async move {
+            // A page limit error is yielded after the items before it.
+            let builder = match builder? {
+                Ok(builder) => builder,
+                Err(limit) => return Some((Err(limit), None)),
+            };
+            let per_page = builder.per_page;
+            let result = builder
+                .call_page::<T>(((builder.page - 1) * per_page) as usize)
+                .await;
+            let next = match &result {
+                Ok(list) => {
+                    let fetched = ((builder.page - 1) * per_page) as usize + list.items.len();
+                    if list.is_last_page(fetched, per_page, builder.skip_total) {
+                        None
+                    } else {
+                        Some(
+                            check_page_limit(builder.page, builder.max_pages, fetched)
+                                .map(|()| builder.page(builder.page + 1)),
+                        )
+                    }
+                }
+                Err(_) => None,
+            };
+            Some((result, next))
+        })
+        .flat_map(|page| {
+            let items = match page {
+                Ok(list) => list.items.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(items)
+        })
+    }
+
+    /// The current filter/sort/expand/fields settings, e.g. to save them.
+    pub fn params(&self) -> QueryParams {
+        QueryParams {
+            filter: self.filter.clone(),
+            sort: self.sort.clone(),
+            expand: self.expand.clone(),
+            fields: self.fields.clone(),
+        }
     }
 
-    pub fn filter(&self, filter_opts: &str) -> Self {
+    /// Replace the filter/sort/expand/fields settings with saved `params`.
+    pub fn with_params(&self, params: QueryParams) -> Self {
         Self {
-            filter: Some(filter_opts.to_string()),
+            filter: params.filter,
+            sort: params.sort,
+            expand: params.expand,
+            fields: params.fields,
             ..self.clone()
         }
     }
 
-    pub fn sort(&self, sort_opts: &str) -> Self {
+    /// Accepts a raw filter string or a [`Filter`](crate::filter::Filter).
+    pub fn filter(&self, filter_opts: impl Into<String>) -> Self {
         Self {
-            sort: Some(sort_opts.to_string()),
+            filter: Some(filter_opts.into()),
             ..self.clone()
         }
     }
 
+    /// Accepts a raw sort string or a [`Sort`](crate::sort::Sort).
+    pub fn sort(&self, sort_opts: impl Into<String>) -> Self {
+        Self {
+            sort: Some(sort_opts.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Expand relation fields, e.g. `author,tags`; decode the result with
+    /// [`Expanded`](crate::models::Expanded).
     pub fn expand(&self, expand_opts: &str) -> Self {
         Self {
             expand: Some(expand_opts.to_string()),
@@ -165,6 +628,45 @@ impl<'a, A: Clone> RecordsListRequestBuilder<'a, A> {
         }
     }
 
+    /// Append an extra `key=value` query parameter, sent after the typed
+    /// ones, for parameters this builder doesn't model.
+    pub fn query_param(&self, key: &str, value: impl Into<String>) -> Self {
+        let mut extra_params = self.extra_params.clone();
+        extra_params.push((key.to_string(), value.into()));
+        Self {
+            extra_params,
+            ..self.clone()
+        }
+    }
+
+    /// Cancel a pending request started with the same `key` when this one
+    /// is sent, e.g. for search-as-you-type. The older request then fails
+    /// with a [`RequestAbortedError`](crate::error::RequestAbortedError).
+    /// [`send_raw`](Self::send_raw) ignores the key.
+    pub fn request_key(&self, key: &str) -> Self {
+        Self {
+            request_key: Some(key.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Only return the given comma separated `fields`, e.g. `id,title`.
+    /// Fields left out must be `Option` or `#[serde(default)]` on `T`.
+    pub fn fields(&self, fields_opts: &str) -> Self {
+        Self {
+            fields: Some(fields_opts.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Set or clear `expand`; `None` and empty strings send no `expand` param.
+    pub fn expand_opt(&self, expand_opts: Option<&str>) -> Self {
+        Self {
+            expand: expand_opts.map(str::to_string),
+            ..self.clone()
+        }
+    }
+
     pub fn page(&self, page: i32) -> Self {
         Self {
             page,
@@ -178,6 +680,48 @@ impl<'a, A: Clone> RecordsListRequestBuilder<'a, A> {
             ..self.clone()
         }
     }
+
+    /// Ask the server to skip counting the matching records, which speeds up
+    /// large listings. `total_items` is then not meaningful.
+    pub fn skip_total(&self, skip_total: bool) -> Self {
+        Self {
+            skip_total,
+            ..self.clone()
+        }
+    }
+
+    /// Stop [`get_all`](Self::get_all), [`stream`](Self::stream) and the
+    /// other paging methods once `token` is cancelled. They then fail with a
+    /// [`CancelledError`](crate::error::CancelledError); the stream yields it
+    /// as its last item.
+    #[cfg(feature = "cancellation")]
+    pub fn cancel_on(&self, token: CancellationToken) -> Self {
+        Self {
+            cancel: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Cap how many pages `get_all` fetches before giving up on reaching the
+    /// end with a [`PageLimitError`].
+    pub fn max_pages(&self, max_pages: i32) -> Self {
+        Self {
+            max_pages,
+            ..self.clone()
+        }
+    }
+
+    /// Override the client's retry policy for this request.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
 }
 
 pub struct RecordViewRequestBuilder<'a, A> {
@@ -185,32 +729,80 @@ pub struct RecordViewRequestBuilder<'a, A> {
     pub collection_name: &'a str,
     pub identifier: &'a str,
     pub expand: Option<String>,
+    pub fields: Option<String>,
+    pub retry: Option<RetryPolicy>,
+    /// Cancels older pending requests; see [`request_key`](Self::request_key).
+    pub request_key: Option<String>,
+}
+
+impl<'a, A> Clone for RecordViewRequestBuilder<'a, A> {
+    fn clone(&self) -> Self {
+        RecordViewRequestBuilder {
+            client: self.client,
+            collection_name: self.collection_name,
+            identifier: self.identifier,
+            expand: self.expand.clone(),
+            fields: self.fields.clone(),
+            retry: self.retry.clone(),
+            request_key: self.request_key.clone(),
+        }
+    }
 }
 
 impl<'a, A> RecordViewRequestBuilder<'a, A> {
-    pub async fn call<T: Default + DeserializeOwned>(&self) -> Result<T, RecordViewError> {
-        let url = format!(
-            "{}/api/collections/{}/records/{}",
-            self.client.base_url, self.collection_name, self.identifier
-        );
+    fn url(&self) -> String {
+        self.client.endpoint(&format!(
+            "collections/{}/records/{}",
+            self.collection_name, self.identifier
+        ))
+    }
+
+    /// Send the view request and hand back the raw response without reading
+    /// its body, for custom streaming or header inspection.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.url();
         let mut build_opts: Vec<(&str, &str)> = vec![];
-        if let Some(expand_opts) = &self.expand {
+        if let Some(expand_opts) = self.expand.as_deref().filter(|e| !e.is_empty()) {
             build_opts.push(("expand", expand_opts))
         }
-        
-        let resp = Httpc::get(self.client, &url, Some(build_opts))
+        if let Some(fields_opts) = &self.fields {
+            build_opts.push(("fields", fields_opts))
+        }
+
+        Httpc::get_with(self.client, &url, Some(build_opts), self.retry.as_ref()).await
+    }
+
+    pub async fn call<T: Default + DeserializeOwned>(&self) -> Result<T, RecordViewError> {
+        self.call_with_meta().await.map(|(_, record)| record)
+    }
+
+    /// Like [`call`](Self::call), also returning the response status and
+    /// headers, e.g. to inspect rate limits.
+    pub async fn call_with_meta<T: Default + DeserializeOwned>(
+        &self,
+    ) -> Result<(ResponseMeta, T), RecordViewError> {
+        self.client
+            .run_keyed(self.request_key.as_deref(), self.fetch_with_meta())
             .await
-            .with_context(|| format!("GET {} failed to execute", url))?;
+    }
+
+    async fn fetch_with_meta<T: Default + DeserializeOwned>(
+        &self,
+    ) -> Result<(ResponseMeta, T), RecordViewError> {
+        let url = self.url();
+        let resp = self.send_raw().await?;
+        let meta = ResponseMeta::of(&resp);
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(RecordViewError::RateLimited {
+                retry_after: Httpc::retry_after(&resp),
+            });
+        }
 
         let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .with_context(|| format!("Reading response body from {} failed", url))?;
+        let body = read_body(self.client, resp, &url).await?;
 
         if !status.is_success() {
-            let snippet_len = cmp::min(2000, body.len());
-            let snippet = &body[..snippet_len];
+            let snippet = body_snippet(&body);
             let code = status.as_u16();
             return if code == 404 {
                 Err(RecordViewError::NotFound {
@@ -229,53 +821,187 @@ impl<'a, A> RecordViewRequestBuilder<'a, A> {
 
         let mut deserializer = serde_json::Deserializer::from_str(&body);
         match serde_path_to_error::deserialize::<_, T>(&mut deserializer) {
-            Ok(parsed) => Ok(parsed),
+            Ok(parsed) => Ok((meta, parsed)),
             Err(de_err) => {
                 // Show a short snippet to help diagnose server-side data issues
-                let snippet_len = cmp::min(2000, body.len());
-                let snippet = &body[..snippet_len];
-
                 Err(RecordViewError::Decode {
                     path: de_err.path().to_string(),
                     source: de_err,
-                    body_snippet: snippet.to_string(),
+                    body_snippet: body_snippet(&body).to_string(),
                 })
             }
         }
     }
 
+    /// Like [`call`](Self::call), but decode the record while it is received
+    /// instead of buffering the whole body first, for very large records.
+    /// Decode errors only show the body up to where decoding failed.
+    pub async fn call_streaming<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.client
+            .run_keyed(self.request_key.as_deref(), self.fetch_streaming())
+            .await
+    }
+
+    async fn fetch_streaming<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = self.url();
+        let resp = self.send_raw().await?;
+        rate_limited(&resp, &url)?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = read_body(self.client, resp, &url).await?;
+            return Err(anyhow!(
+                "Request to {} failed: HTTP {}.\nResponse (truncated):\n{}",
+                url,
+                status.as_u16(),
+                body_snippet(&body)
+            ));
+        }
+
+        decode_streaming(self.client, resp, &url).await
+    }
+
+    /// Expand relation fields, e.g. `author,tags`; decode the result with
+    /// [`Expanded`](crate::models::Expanded).
     pub fn expand(&self, expand_opts: &str) -> Self {
         Self {
             expand: Some(expand_opts.to_string()),
-            ..*self
+            ..self.clone()
+        }
+    }
+
+    /// Only return the given comma separated `fields`, e.g. `id,title`.
+    /// Fields left out must be `Option` or `#[serde(default)]` on `T`.
+    pub fn fields(&self, fields_opts: &str) -> Self {
+        Self {
+            fields: Some(fields_opts.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Set or clear `expand`; `None` and empty strings send no `expand` param.
+    pub fn expand_opt(&self, expand_opts: Option<&str>) -> Self {
+        Self {
+            expand: expand_opts.map(str::to_string),
+            ..self.clone()
+        }
+    }
+
+    /// Override the client's retry policy for this request.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
+
+    /// Cancel a pending request started with the same `key` when this one
+    /// is sent. The older request then fails with a
+    /// [`RequestAbortedError`](crate::error::RequestAbortedError).
+    /// [`send_raw`](Self::send_raw) ignores the key.
+    pub fn request_key(&self, key: &str) -> Self {
+        Self {
+            request_key: Some(key.to_string()),
+            ..self.clone()
         }
     }
 }
 
 impl<'a, A> RecordDestroyRequestBuilder<'a, A> {
+    /// Send the delete request and hand back the raw response.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.client.endpoint(&format!(
+            "collections/{}/records/{}",
+            self.collection_name, self.identifier
+        ));
+        Httpc::delete_with(self.client, url.as_str(), self.retry.as_ref()).await
+    }
+
     pub async fn call(&self) -> Result<()> {
-        let url = format!(
-            "{}/api/collections/{}/records/{}",
-            self.client.base_url, self.collection_name, self.identifier
-        );
-        match Httpc::delete(self.client, url.as_str()).await {
-            Ok(result) => {
-                if result.status() == 204 {
-                    Ok(())
-                } else {
-                    Err(anyhow!("Failed to delete"))
-                }
+        self.call_with_status().await.map(|_| ())
+    }
+
+    /// Like [`call`](Self::call), also returning the response status.
+    pub async fn call_with_status(&self) -> Result<StatusCode> {
+        let result = self.send_raw().await?;
+        rate_limited(&result, result.url().as_str())?;
+        if result.status() == 204 {
+            Ok(result.status())
+        } else {
+            Err(anyhow!("Failed to delete"))
+        }
+    }
+}
+
+impl<'a, A: Clone> RecordDestroyRequestBuilder<'a, A> {
+    /// Override the client's retry policy for this request.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        self.retry(RetryPolicy::none())
+    }
+}
+
+/// What [`RecordsManager::upsert`] or [`RecordsManager::create_or_get`] did,
+/// along with the record as stored by the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<T> {
+    /// No matching record existed, so one was created.
+    Created(T),
+    /// The existing record was updated.
+    Updated(T),
+    /// A matching record already existed and was left unchanged.
+    Existing(T),
+}
+
+impl<T> Outcome<T> {
+    pub fn record(&self) -> &T {
+        match self {
+            Outcome::Created(record) | Outcome::Updated(record) | Outcome::Existing(record) => {
+                record
+            }
+        }
+    }
+
+    pub fn into_record(self) -> T {
+        match self {
+            Outcome::Created(record) | Outcome::Updated(record) | Outcome::Existing(record) => {
+                record
             }
-            Err(e) => Err(anyhow!("error: {}", e)),
         }
     }
 }
 
+/// The outcome of [`RecordsManager::delete_many`], per record id.
+#[derive(Debug, Default)]
+pub struct DeleteReport {
+    pub deleted: Vec<String>,
+    /// Ids that did not exist (HTTP 404), e.g. because they were already deleted.
+    pub not_found: Vec<String>,
+    pub failed: Vec<(String, anyhow::Error)>,
+}
+
 #[derive(Clone, Debug)]
 pub struct RecordDestroyRequestBuilder<'a, A> {
     pub identifier: &'a str,
     pub client: &'a Client<A>,
     pub collection_name: &'a str,
+    pub retry: Option<RetryPolicy>,
 }
 
 #[derive(Debug, Clone)]
@@ -283,6 +1009,47 @@ pub struct RecordDeleteAllRequestBuilder<'a, A> {
     pub client: &'a Client<A>,
     pub collection_name: &'a str,
     pub filter: Option<&'a str>,
+    pub concurrency: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RecordId {
+    id: String,
+}
+
+impl<'a, A: Clone + WriteAccess> RecordDeleteAllRequestBuilder<'a, A> {
+    /// Delete every record matching the filter and return how many were
+    /// deleted. Matching ids are listed first, page by page, then deleted;
+    /// records that disappear in between (HTTP 404) are skipped, and the
+    /// first other failure is returned once all deletes were attempted.
+    pub async fn call(&self) -> Result<usize> {
+        let records = RecordsManager {
+            client: self.client,
+            name: self.collection_name,
+        };
+        let ids = records.list_ids(self.filter).await?;
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        let report = records.delete_many(&ids, self.concurrency).await?;
+        let deleted = report.deleted.len();
+        match report.failed.into_iter().next() {
+            Some((id, e)) => Err(e.context(format!(
+                "Deleting record {} failed after {} of {} records were deleted",
+                id,
+                deleted,
+                ids.len()
+            ))),
+            None => Ok(deleted),
+        }
+    }
+
+    /// Send up to `concurrency` deletes at a time; one by default.
+    pub fn concurrency(&self, concurrency: usize) -> Self {
+        Self {
+            concurrency,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -290,6 +1057,129 @@ pub struct RecordCreateRequestBuilder<'a, A, T: Serialize + Clone> {
     pub client: &'a Client<A>,
     pub collection_name: &'a str,
     pub record: T,
+    pub files: Vec<FileUpload>,
+    pub retry: Option<RetryPolicy>,
+    pub schema: Option<Collection>,
+    /// Leave out `null` fields; see [`omit_none`](Self::omit_none).
+    pub omit_none: bool,
+}
+
+/// Where the contents of a multipart file part are read from.
+#[derive(Debug, Clone)]
+pub enum FileSource {
+    /// Streamed from disk when the request is sent, never fully buffered.
+    Path(PathBuf),
+    /// Contents already held in memory.
+    Bytes(Vec<u8>),
+}
+
+/// A file attached to a record create request, sent as a multipart part.
+#[derive(Debug, Clone)]
+pub struct FileUpload {
+    pub field: String,
+    pub filename: String,
+    pub mime: String,
+    pub source: FileSource,
+}
+
+impl FileUpload {
+    async fn into_part(self) -> Result<Part> {
+        let part = match self.source {
+            FileSource::Path(path) => {
+                let file = tokio::fs::File::open(&path)
+                    .await
+                    .with_context(|| format!("Opening {} failed", path.display()))?;
+                let length = file.metadata().await?.len();
+                let body = Body::wrap_stream(ReaderStream::new(file));
+                Part::stream_with_length(body, length)
+            }
+            FileSource::Bytes(bytes) => Part::bytes(bytes),
+        };
+        Ok(part.file_name(self.filename).mime_str(&self.mime)?)
+    }
+}
+
+fn validate_against<T: Serialize>(
+    schema: Option<&Collection>,
+    record: &T,
+) -> Result<(), RecordWriteError> {
+    if let Some(collection) = schema {
+        let record = serde_json::to_value(record).map_err(anyhow::Error::from)?;
+        collection.validate_record(&record)?;
+    }
+    Ok(())
+}
+
+/// The JSON body for `record`, without its top-level `null` fields when
+/// `omit_none` is set.
+fn json_payload<T: Serialize>(record: &T, omit_none: bool) -> Result<String> {
+    if !omit_none {
+        return Ok(serde_json::to_string(record)?);
+    }
+    let mut record = serde_json::to_value(record)?;
+    if let Value::Object(fields) = &mut record {
+        fields.retain(|_, value| !value.is_null());
+    }
+    Ok(serde_json::to_string(&record)?)
+}
+
+/// Decode a create/update response, turning a 4xx body into the per-field
+/// [`ErrorResponse`] the server sends.
+async fn read_write_response<R: DeserializeOwned>(
+    resp: Response,
+) -> Result<(StatusCode, R), RecordWriteError> {
+    let status = resp.status();
+    let url = resp.url().to_string();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(RecordWriteError::RateLimited {
+            retry_after: Httpc::retry_after(&resp),
+        });
+    }
+    let body = resp
+        .text()
+        .await
+        .with_context(|| format!("Reading response body from {} failed", url))?;
+
+    if !status.is_success() {
+        if status.is_client_error() {
+            if let Ok(err) = serde_json::from_str::<ErrorResponse>(&body) {
+                return Err(RecordWriteError::Validation(err));
+            }
+        }
+        return Err(RecordWriteError::Http {
+            status: status.as_u16(),
+            url,
+            body_snippet: body_snippet(&body).to_string(),
+        });
+    }
+
+    if body.trim().is_empty() {
+        return Err(RecordWriteError::EmptyBody {
+            status: status.as_u16(),
+            url,
+        });
+    }
+    match serde_json::from_str(&body) {
+        Ok(response) => Ok((status, response)),
+        Err(source) => {
+            // Some hooks and misconfigured proxies answer 2xx with an error body.
+            if let Ok(err) = serde_json::from_str::<ErrorResponse>(&body) {
+                return Err(RecordWriteError::Validation(err));
+            }
+            Err(RecordWriteError::Decode {
+                source,
+                body_snippet: body_snippet(&body).to_string(),
+            })
+        }
+    }
+}
+
+/// The content type for `filename` by extension, or `application/octet-stream`.
+fn guess_mime(filename: &str) -> String {
+    mime_guess::from_path(filename)
+        .first_or_octet_stream()
+        .essence_str()
+        .to_string()
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -299,47 +1189,210 @@ pub struct CreateResponse {
     #[serde(rename = "@collectionId")]
     pub collection_id: Option<String>,
     pub id: String,
-    pub updated: String,
-    pub created: String,
+    pub updated: DateTime<Utc>,
+    pub created: DateTime<Utc>,
 }
 
 impl<'a, A, T: Serialize + Clone> RecordCreateRequestBuilder<'a, A, T> {
-    pub async fn call(&self) -> Result<CreateResponse> {
-        let url = format!(
-            "{}/api/collections/{}/records",
-            self.client.base_url, self.collection_name
-        );
-        let payload = serde_json::to_string(&self.record).map_err(anyhow::Error::from)?;
-        match Httpc::post(self.client, &url, payload).await {
-            Ok(result) => {
-                let response = result.json::<CreateResponse>().await?;
-                Ok(response)
+    /// Send the create request and hand back the raw response without
+    /// reading its body.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self
+            .client
+            .endpoint(&format!("collections/{}/records", self.collection_name));
+        validate_against(self.schema.as_ref(), &self.record)?;
+        if self.files.is_empty() {
+            let payload = json_payload(&self.record, self.omit_none)?;
+            Httpc::post_with(self.client, &url, payload, self.retry.as_ref()).await
+        } else {
+            let form = self.multipart_form().await?;
+            Httpc::post_multipart(self.client, &url, form).await
+        }
+    }
+
+    pub async fn call(&self) -> Result<CreateResponse, RecordWriteError> {
+        self.call_with_status().await.map(|(_, response)| response)
+    }
+
+    /// Like [`call`](Self::call), also returning the response status.
+    pub async fn call_with_status(&self) -> Result<(StatusCode, CreateResponse), RecordWriteError> {
+        validate_against(self.schema.as_ref(), &self.record)?;
+        read_write_response(self.send_raw().await?).await
+    }
+
+    async fn multipart_form(&self) -> Result<Form> {
+        let mut form = Form::new();
+        if let Value::Object(fields) = serde_json::to_value(&self.record)? {
+            for (key, value) in fields {
+                match value {
+                    Value::Null => {}
+                    Value::String(text) => form = form.text(key, text),
+                    other => form = form.text(key, other.to_string()),
+                }
             }
-            Err(e) => Err(anyhow!("error: {}", e)),
+        }
+        for file in self.files.iter().cloned() {
+            let field = file.field.clone();
+            form = form.part(field, file.into_part().await?);
+        }
+        Ok(form)
+    }
+}
+
+impl<'a, A: Clone, T: Serialize + Clone> RecordCreateRequestBuilder<'a, A, T> {
+    /// Attach a file read from `path` to `field`. The file is streamed from
+    /// disk while the request is sent, so large uploads use constant memory.
+    pub fn file_from_path(&self, field: &str, path: &Path) -> Self {
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mime = guess_mime(&filename);
+        self.attach(FileUpload {
+            field: field.to_string(),
+            filename,
+            mime,
+            source: FileSource::Path(path.to_path_buf()),
+        })
+    }
+
+    /// Attach in-memory `bytes` as `filename` to `field`, with the content
+    /// type guessed from the file extension.
+    pub fn file_from_bytes(&self, field: &str, filename: &str, bytes: Vec<u8>) -> Self {
+        self.file_with_mime(field, filename, bytes, &guess_mime(filename))
+    }
+
+    /// Attach in-memory `bytes` as `filename` to `field` with an explicit
+    /// content type, e.g. when the extension is missing or misleading.
+    pub fn file_with_mime(&self, field: &str, filename: &str, bytes: Vec<u8>, mime: &str) -> Self {
+        self.attach(FileUpload {
+            field: field.to_string(),
+            filename: filename.to_string(),
+            mime: mime.to_string(),
+            source: FileSource::Bytes(bytes),
+        })
+    }
+
+    fn attach(&self, file: FileUpload) -> Self {
+        let mut files = self.files.clone();
+        files.push(file);
+        Self {
+            files,
+            ..self.clone()
+        }
+    }
+
+    /// Validate the record against `collection`'s schema before sending,
+    /// e.g. to reject `select` values outside the allowed set.
+    pub fn validate_with(&self, collection: &Collection) -> Self {
+        Self {
+            schema: Some(collection.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Leave `None` fields out of the request instead of sending them as
+    /// `null`, so the server applies its defaults. File uploads never send
+    /// `null` fields.
+    pub fn omit_none(&self) -> Self {
+        Self {
+            omit_none: true,
+            ..self.clone()
+        }
+    }
+
+    /// Opt this request into retries. Creates are never retried by default;
+    /// multipart uploads cannot be replayed and are always sent once.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        Self {
+            retry: None,
+            ..self.clone()
         }
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct RecordUpdateRequestBuilder<'a, A, T: Serialize + Clone> {
     pub record: T,
     pub collection_name: &'a str,
     pub client: &'a Client<A>,
     pub id: &'a str,
+    pub retry: Option<RetryPolicy>,
+    pub schema: Option<Collection>,
+    /// Leave out `null` fields; see [`omit_none`](Self::omit_none).
+    pub omit_none: bool,
 }
 
 impl<'a, A, T: Serialize + Clone> RecordUpdateRequestBuilder<'a, A, T> {
-    pub async fn call(&self) -> Result<T> {
-        let url = format!(
-            "{}/api/collections/{}/records/{}",
-            self.client.base_url, self.collection_name, self.id
-        );
-        let payload = serde_json::to_string(&self.record).map_err(anyhow::Error::from)?;
-        match Httpc::patch(self.client, &url, payload).await {
-            Ok(result) => {
-                result.json::<CreateResponse>().await?;
-                Ok(self.record.clone())
-            }
-            Err(e) => Err(anyhow!("error: {}", e)),
+    /// Send the update request and hand back the raw response without
+    /// reading its body.
+    pub async fn send_raw(&self) -> Result<Response> {
+        let url = self.client.endpoint(&format!(
+            "collections/{}/records/{}",
+            self.collection_name, self.id
+        ));
+        validate_against(self.schema.as_ref(), &self.record)?;
+        let payload = json_payload(&self.record, self.omit_none)?;
+        Httpc::patch_with(self.client, &url, payload, self.retry.as_ref()).await
+    }
+
+    /// Send the update and return the record as stored by the server,
+    /// including server-side changes such as the `updated` timestamp.
+    pub async fn call(&self) -> Result<T, RecordWriteError>
+    where
+        T: DeserializeOwned,
+    {
+        self.call_with_status().await.map(|(_, record)| record)
+    }
+
+    /// Like [`call`](Self::call), also returning the response status.
+    pub async fn call_with_status(&self) -> Result<(StatusCode, T), RecordWriteError>
+    where
+        T: DeserializeOwned,
+    {
+        validate_against(self.schema.as_ref(), &self.record)?;
+        read_write_response(self.send_raw().await?).await
+    }
+}
+
+impl<'a, A: Clone, T: Serialize + Clone> RecordUpdateRequestBuilder<'a, A, T> {
+    /// Validate the record against `collection`'s schema before sending,
+    /// e.g. to reject `select` values outside the allowed set.
+    pub fn validate_with(&self, collection: &Collection) -> Self {
+        Self {
+            schema: Some(collection.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Leave `None` fields out of the request instead of sending them as
+    /// `null`, so a partial update only changes the fields that are set.
+    pub fn omit_none(&self) -> Self {
+        Self {
+            omit_none: true,
+            ..self.clone()
+        }
+    }
+
+    /// Opt this request into retries. Updates are never retried by default.
+    pub fn retry(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: Some(policy),
+            ..self.clone()
+        }
+    }
+
+    pub fn no_retry(&self) -> Self {
+        Self {
+            retry: None,
+            ..self.clone()
         }
     }
 }
@@ -351,14 +1404,136 @@ impl<'a, A> RecordsManager<'a, A> {
             client: self.client,
             collection_name: self.name,
             expand: None,
+            fields: None,
+            retry: None,
+            request_key: None,
+        }
+    }
+
+    /// The record matching `filter`, e.g. a user by email, or `None` when no
+    /// record matches. When several match, the first one in the server's
+    /// default order is returned; use [`list`](Self::list) with a `sort` to
+    /// choose among them.
+    pub async fn first<T: DeserializeOwned>(&self, filter: impl Into<String>) -> Result<Option<T>>
+    where
+        A: Clone,
+    {
+        match self.first_value(filter).await? {
+            Some(found) => Ok(Some(serde_json::from_value(found)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn first_value(&self, filter: impl Into<String>) -> Result<Option<Value>>
+    where
+        A: Clone,
+    {
+        let page = self
+            .list()
+            .filter(filter)
+            .per_page(1)
+            .skip_total(true)
+            .call::<Value>()
+            .await?;
+        Ok(page.items.into_iter().next())
+    }
+
+    /// The auth record with `email`, e.g. decoded into a struct embedding
+    /// [`AuthRecord`](crate::models::AuthRecord), or `None` when there is
+    /// none or its email is hidden from this client.
+    pub async fn find_by_email<T: DeserializeOwned>(&self, email: &str) -> Result<Option<T>>
+    where
+        A: Clone,
+    {
+        self.first(Filter::email(email)?).await
+    }
+
+    /// The number of records matching `filter`, or of all records. Only one
+    /// item is requested and its fields are skipped, so this is far cheaper
+    /// than counting the result of [`get_all`](Self::get_all).
+    pub async fn count(&self, filter: Option<&str>) -> Result<i32>
+    where
+        A: Clone,
+    {
+        let list = self.list().fields("id").per_page(1);
+        let list = match filter {
+            Some(filter) => list.filter(filter),
+            None => list,
+        };
+        Ok(list.call::<IgnoredAny>().await?.total_items)
+    }
+
+    /// The ids of all records matching `filter`, or of all records, e.g. to
+    /// find records deleted on the server. Only the `id` field is requested,
+    /// page by page.
+    pub async fn list_ids(&self, filter: Option<&str>) -> Result<Vec<String>>
+    where
+        A: Clone,
+    {
+        let list = self.list().fields("id");
+        let list = match filter {
+            Some(filter) => list.filter(filter),
+            None => list,
+        };
+        let records = list.get_all::<RecordId>().await?;
+        Ok(records.into_iter().map(|record| record.id).collect())
+    }
+
+    /// View a record as a field map, for code without a compile-time schema.
+    pub async fn view_dynamic(
+        &self,
+        identifier: &'a str,
+    ) -> Result<Map<String, Value>, RecordViewError> {
+        self.view(identifier).call::<Map<String, Value>>().await
+    }
+
+    /// List the first page of records as field maps.
+    pub async fn list_dynamic(&self) -> Result<RecordList<Map<String, Value>>, RecordListError>
+    where
+        A: Clone,
+    {
+        self.list().call::<Map<String, Value>>().await
+    }
+
+    pub fn list(&self) -> RecordsListRequestBuilder<'a, A> {
+        RecordsListRequestBuilder {
+            client: self.client,
+            collection_name: self.name,
+            filter: None,
+            sort: None,
+            expand: None,
+            fields: None,
+            page: 1,
+            per_page: 100,
+            retry: None,
+            max_pages: DEFAULT_MAX_PAGES,
+            skip_total: false,
+            extra_params: Vec::new(),
+            request_key: None,
+            #[cfg(feature = "cancellation")]
+            cancel: None,
         }
     }
 
+    pub async fn get_all<T>(&self) -> Result<Vec<T>>
+    where
+        A: Clone,
+        T: Default + DeserializeOwned,
+    {
+        self.list().get_all().await
+    }
+}
+
+/// Writes need a [`WriteAccess`] client state: authenticated, or explicitly
+/// opted in with [`Client::with_public_writes`] for collections whose API
+/// rules allow anonymous writes.
+impl<'a, A: WriteAccess> RecordsManager<'a, A> {
     pub fn destroy(&self, identifier: &'a str) -> RecordDestroyRequestBuilder<'a, A> {
         RecordDestroyRequestBuilder {
             identifier,
             client: self.client,
             collection_name: self.name,
+            retry: None,
         }
     }
 
@@ -372,6 +1547,9 @@ impl<'a, A> RecordsManager<'a, A> {
             collection_name: self.name,
             id: identifier,
             record,
+            retry: None,
+            schema: None,
+            omit_none: false,
         }
     }
 
@@ -380,47 +1558,112 @@ impl<'a, A> RecordsManager<'a, A> {
             record,
             client: self.client,
             collection_name: self.name,
+            files: Vec::new(),
+            retry: None,
+            schema: None,
+            omit_none: false,
         }
     }
 
-    pub fn list(&self) -> RecordsListRequestBuilder<'a, A> {
-        RecordsListRequestBuilder {
+    /// Delete every record matching `filter`, e.g. `created < "2024-01-01"`.
+    pub fn delete_all(&self, filter: &'a str) -> RecordDeleteAllRequestBuilder<'a, A> {
+        RecordDeleteAllRequestBuilder {
             client: self.client,
             collection_name: self.name,
-            filter: None,
-            sort: None,
-            expand: None,
-            page: 1,
-            per_page: 100,
+            filter: Some(filter),
+            concurrency: 1,
         }
     }
 
-    pub async fn get_all<T>(&self) -> Result<Vec<T>>
-    where
-        A: Clone,
-        T: Default + DeserializeOwned,
-    {
-        let mut all_items = Vec::new();
-        let mut page = 1;
-        let per_page = 1000;
+    /// Delete the records with the given `ids`, at most `concurrency` at a time.
+    /// Failures do not stop the remaining deletes; they are listed in the report.
+    pub async fn delete_many(&self, ids: &[&str], concurrency: usize) -> Result<DeleteReport> {
+        let mut results = stream::iter(ids.iter().copied())
+            .map(|id| async move {
+                let request = RecordDestroyRequestBuilder {
+                    identifier: id,
+                    client: self.client,
+                    collection_name: self.name,
+                    retry: None,
+                };
+                (id, request.send_raw().await)
+            })
+            .buffer_unordered(concurrency.max(1));
 
-        loop {
-            let page_resp = self
-                .list()
-                .page(page)
-                .per_page(per_page)
-                .call::<T>()
-                .await?;
+        let mut report = DeleteReport::default();
+        while let Some((id, result)) = results.next().await {
+            match result {
+                Ok(resp) if resp.status() == StatusCode::NO_CONTENT => {
+                    report.deleted.push(id.to_string())
+                }
+                Ok(resp) if resp.status() == StatusCode::NOT_FOUND => {
+                    report.not_found.push(id.to_string())
+                }
+                Ok(resp) => report.failed.push((
+                    id.to_string(),
+                    anyhow!("Failed to delete: HTTP {}", resp.status().as_u16()),
+                )),
+                Err(e) => report.failed.push((id.to_string(), e)),
+            }
+        }
+        Ok(report)
+    }
 
-            all_items.extend(page_resp.items.into_iter());
+    /// Update the record with `identifier`, or create it with that id when it
+    /// does not exist yet.
+    ///
+    /// Any 404 from the update is taken to mean the record is missing, so a
+    /// create is attempted. PocketBase also answers 404 when the update rule
+    /// hides an existing record or the collection does not exist; the create
+    /// then fails and its error is returned, e.g. a validation error for the
+    /// already used id.
+    pub async fn upsert<T>(
+        &self,
+        identifier: &'a str,
+        record: T,
+    ) -> Result<Outcome<T>, RecordWriteError>
+    where
+        T: Serialize + Clone + DeserializeOwned,
+    {
+        match self.update(identifier, record.clone()).call().await {
+            Ok(updated) => return Ok(Outcome::Updated(updated)),
+            Err(RecordWriteError::Validation(err)) if err.status == 404 => {}
+            Err(RecordWriteError::Http { status: 404, .. }) => {}
+            Err(e) => return Err(e),
+        }
 
-            if all_items.len() == page_resp.total_items as usize {
-                break;
-            }
+        let mut fields = serde_json::to_value(&record).map_err(anyhow::Error::from)?;
+        if let Value::Object(fields) = &mut fields {
+            fields.insert("id".to_string(), Value::from(identifier));
+        }
+        let created = self.create(fields).send_raw().await?;
+        let (_, created) = read_write_response(created).await?;
+        Ok(Outcome::Created(created))
+    }
 
-            page += 1;
+    /// Return the first record matching `filter`, or create `record` when
+    /// none does.
+    pub async fn create_or_get<T>(
+        &self,
+        filter: impl Into<String>,
+        record: T,
+    ) -> Result<Outcome<T>, RecordWriteError>
+    where
+        A: Clone,
+        T: Serialize + Clone + DeserializeOwned,
+    {
+        if let Some(found) = self.first_value(filter).await? {
+            let existing = serde_json::from_value(found.clone()).map_err(|source| {
+                RecordWriteError::Decode {
+                    source,
+                    body_snippet: body_snippet(&found.to_string()).to_string(),
+                }
+            })?;
+            return Ok(Outcome::Existing(existing));
         }
 
-        Ok(all_items)
+        let created = self.create(record).send_raw().await?;
+        let (_, created) = read_write_response(created).await?;
+        Ok(Outcome::Created(created))
     }
 }
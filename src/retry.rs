@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+/// When and how often a failed request is retried.
+///
+/// A policy set on the client with `Client::with_retry_policy` applies to
+/// idempotent requests (GET/DELETE) only. Builders can override it per
+/// request with `.retry(policy)` or `.no_retry()`, which is also the only way
+/// to opt a POST/PATCH into retries.
+///
+/// The delay doubles after every attempt, starting at `base_delay` and capped
+/// at `max_delay`. A `Retry-After` header (in seconds) on a 429 response is
+/// honored instead, but still capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Upper bound on the backoff delay and on a server's `Retry-After`.
+    pub max_delay: Duration,
+    /// Response statuses that are worth retrying.
+    pub retry_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            retry_statuses: vec![429, 502, 503, 504],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    pub fn base_delay(self, base_delay: Duration) -> Self {
+        RetryPolicy { base_delay, ..self }
+    }
+
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        RetryPolicy { max_delay, ..self }
+    }
+
+    pub fn retry_statuses(self, retry_statuses: Vec<u16>) -> Self {
+        RetryPolicy {
+            retry_statuses,
+            ..self
+        }
+    }
+
+    pub(crate) fn should_retry_status(&self, status: u16) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// The wait before retrying after failed attempt number `attempt` (from 1).
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
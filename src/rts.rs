@@ -1 +1,478 @@
+use crate::client::Client;
+use crate::httpc::Httpc;
+use anyhow::{anyhow, Context, Result};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
 
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Subscription changes made within this window are sent as one update.
+const SUBSCRIBE_BATCH_WINDOW: Duration = Duration::from_millis(25);
+
+pub struct RealtimeManager<'a, A> {
+    pub client: &'a Client<A>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RealtimeAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// The state of the realtime connection, as reported by
+/// [`Realtime::connection_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Opening the first connection.
+    Connecting,
+    /// The server accepted the connection and assigned a client id.
+    Connected,
+    /// The connection dropped and is being re-established.
+    Reconnecting,
+    /// The last [`Realtime`] handle was dropped.
+    Disconnected,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RealtimeEvent<T> {
+    /// The subscription topic the event was delivered for, e.g. `posts`.
+    #[serde(skip)]
+    pub topic: String,
+    pub action: RealtimeAction,
+    pub record: T,
+}
+
+#[derive(Debug)]
+struct RawEvent {
+    topic: String,
+    data: String,
+}
+
+/// What a subscription stream receives: an event, or why its topics could
+/// not be subscribed.
+type Delivery = Result<RawEvent, String>;
+
+#[derive(Default)]
+struct State {
+    client_id: Option<String>,
+    topics: BTreeMap<String, Vec<UnboundedSender<Delivery>>>,
+    /// The topic list changed since it was last sent to the server.
+    dirty: bool,
+    /// Topics added since the server last accepted the topic list.
+    unsent: BTreeSet<String>,
+}
+
+impl State {
+    /// Tell each stream listening to one of `topics` that subscribing failed.
+    fn report_failed(&mut self, topics: &BTreeSet<String>, error: &anyhow::Error) {
+        let mut notified: Vec<&UnboundedSender<Delivery>> = Vec::new();
+        for topic in topics {
+            for listener in self.topics.get(topic).into_iter().flatten() {
+                if notified.iter().any(|seen| seen.same_channel(listener)) {
+                    continue;
+                }
+                let _ = listener.send(Err(format!(
+                    "Updating realtime subscriptions failed: {:#}",
+                    error
+                )));
+                notified.push(listener);
+            }
+        }
+    }
+}
+
+struct Shared<A> {
+    client: Client<A>,
+    state: Mutex<State>,
+    changed: Notify,
+    connection_state: watch::Sender<ConnectionState>,
+}
+
+impl<A> Shared<A> {
+    /// Publish `next`, notifying watchers only when the state actually changes.
+    fn set_connection_state(&self, next: ConnectionState) {
+        self.connection_state.send_if_modified(|current| {
+            let changed = *current != next;
+            *current = next;
+            changed
+        });
+    }
+}
+
+struct Connection<A> {
+    shared: Arc<Shared<A>>,
+    task: JoinHandle<()>,
+    flusher: JoinHandle<()>,
+}
+
+impl<A> Drop for Connection<A> {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.flusher.abort();
+        self.shared
+            .set_connection_state(ConnectionState::Disconnected);
+    }
+}
+
+/// A live connection to the PocketBase realtime API.
+///
+/// The connection is kept open in a background task and is re-established,
+/// with the current subscriptions re-sent, whenever it drops. Cloning the
+/// handle shares the connection; it is closed once the last handle is dropped.
+pub struct Realtime<A> {
+    connection: Arc<Connection<A>>,
+}
+
+impl<A> Clone for Realtime<A> {
+    fn clone(&self) -> Self {
+        Realtime {
+            connection: Arc::clone(&self.connection),
+        }
+    }
+}
+
+/// Typed events for the topics passed to [`Realtime::subscribe`], or an error
+/// when an event can't be decoded or the server rejected the subscription.
+pub struct RealtimeStream<T> {
+    receiver: UnboundedReceiver<Delivery>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Stream for RealtimeStream<T> {
+    type Item = Result<RealtimeEvent<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|delivery| {
+            delivery.map(|delivery| {
+                let RawEvent { topic, data } = delivery.map_err(|error| anyhow!(error))?;
+                let mut event = serde_json::from_str::<RealtimeEvent<T>>(&data)
+                    .with_context(|| format!("Decoding realtime event for `{}` failed", topic))?;
+                event.topic = topic;
+                Ok(event)
+            })
+        })
+    }
+}
+
+impl<'a, A: Clone + Send + Sync + 'static> RealtimeManager<'a, A> {
+    /// Open the realtime event stream in a background task.
+    pub fn connect(&self) -> Realtime<A> {
+        let shared = Arc::new(Shared {
+            client: self.client.clone(),
+            state: Mutex::new(State::default()),
+            changed: Notify::new(),
+            connection_state: watch::channel(ConnectionState::Connecting).0,
+        });
+        let task = tokio::spawn(run(Arc::clone(&shared)));
+        let flusher = tokio::spawn(flush_changes(Arc::clone(&shared)));
+        Realtime {
+            connection: Arc::new(Connection {
+                shared,
+                task,
+                flusher,
+            }),
+        }
+    }
+}
+
+impl<A: Clone + Send + Sync + 'static> Realtime<A> {
+    /// Subscribe to `topics` (e.g. `posts` or `posts/RECORD_ID`) and return a
+    /// stream of their events decoded into `T`.
+    ///
+    /// Topics that are already subscribed on this connection are shared with
+    /// the existing subscription rather than registered with the server again.
+    /// New topics are sent to the server together with any other changes made
+    /// within a short window; call [`Realtime::flush`] to send them right away.
+    /// If the server rejects them, the returned stream yields the error and
+    /// the change is sent again with the next change or flush. Once every
+    /// stream for a topic is dropped, the topic is unsubscribed when its next
+    /// event arrives.
+    pub async fn subscribe<T: DeserializeOwned>(
+        &self,
+        topics: &[&str],
+    ) -> Result<RealtimeStream<T>> {
+        let (sender, receiver) = unbounded_channel();
+        let shared = &self.connection.shared;
+        {
+            let mut state = shared.state.lock().unwrap();
+            let mut added = false;
+            let mut requested = topics.to_vec();
+            requested.sort_unstable();
+            requested.dedup();
+            for topic in requested {
+                match state.topics.get_mut(topic) {
+                    Some(listeners) => {
+                        log::debug!("realtime topic `{}` is already subscribed", topic);
+                        listeners.push(sender.clone());
+                    }
+                    None => {
+                        state.topics.insert(topic.to_string(), vec![sender.clone()]);
+                        state.unsent.insert(topic.to_string());
+                        added = true;
+                    }
+                }
+            }
+            if added {
+                state.dirty = true;
+                shared.changed.notify_one();
+            }
+        }
+
+        Ok(RealtimeStream {
+            receiver,
+            marker: PhantomData,
+        })
+    }
+
+    /// Subscribe to `collection` and return a dedicated channel of its events
+    /// decoded into `T`, so each collection can be handled by its own task.
+    ///
+    /// All channels share this connection; events are routed by topic.
+    pub async fn channel<T>(
+        &self,
+        collection: &str,
+    ) -> Result<UnboundedReceiver<Result<RealtimeEvent<T>>>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut events = self.subscribe::<T>(&[collection]).await?;
+        let (sender, receiver) = unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    /// Stop receiving events for `topics`. Streams whose topics are all
+    /// unsubscribed end.
+    ///
+    /// Like [`Realtime::subscribe`], the change is batched; call
+    /// [`Realtime::flush`] to send it right away.
+    pub async fn unsubscribe(&self, topics: &[&str]) -> Result<()> {
+        let shared = &self.connection.shared;
+        let mut state = shared.state.lock().unwrap();
+        let mut removed = false;
+        for topic in topics {
+            removed |= state.topics.remove(*topic).is_some();
+            state.unsent.remove(*topic);
+        }
+        if removed {
+            state.dirty = true;
+            shared.changed.notify_one();
+        }
+        Ok(())
+    }
+
+    /// The client id the server assigned to the current connection, or `None`
+    /// while connecting.
+    pub fn client_id(&self) -> Option<String> {
+        self.connection
+            .shared
+            .state
+            .lock()
+            .unwrap()
+            .client_id
+            .clone()
+    }
+
+    /// The topics currently subscribed on this connection.
+    pub fn topics(&self) -> Vec<String> {
+        let state = self.connection.shared.state.lock().unwrap();
+        state.topics.keys().cloned().collect()
+    }
+
+    /// Watch the connection state, e.g. to show whether events are live.
+    pub fn connection_state(&self) -> watch::Receiver<ConnectionState> {
+        self.connection.shared.connection_state.subscribe()
+    }
+
+    /// Send pending subscription changes to the server now instead of waiting
+    /// for the batching window to close. Changes whose batched send failed
+    /// are sent again.
+    pub async fn flush(&self) -> Result<()> {
+        flush_pending(&self.connection.shared).await
+    }
+}
+
+async fn flush_changes<A>(shared: Arc<Shared<A>>) {
+    loop {
+        shared.changed.notified().await;
+        tokio::time::sleep(SUBSCRIBE_BATCH_WINDOW).await;
+        if let Err(e) = flush_pending(&shared).await {
+            log::warn!("updating realtime subscriptions failed: {:#}", e);
+        }
+    }
+}
+
+async fn flush_pending<A>(shared: &Shared<A>) -> Result<()> {
+    // Before the first PB_CONNECT the listener sends the topics itself.
+    let pending = {
+        let mut state = shared.state.lock().unwrap();
+        match state.client_id.clone() {
+            Some(client_id) if state.dirty => {
+                state.dirty = false;
+                let topics = state.topics.keys().cloned().collect::<Vec<_>>();
+                Some((client_id, topics, std::mem::take(&mut state.unsent)))
+            }
+            _ => None,
+        }
+    };
+    if let Some((client_id, topics, added)) = pending {
+        if let Err(e) = submit_subscriptions(&shared.client, &client_id, &topics).await {
+            let mut state = shared.state.lock().unwrap();
+            state.dirty = true;
+            state.report_failed(&added, &e);
+            // Topics unsubscribed in the meantime no longer need sending.
+            let still_added: Vec<_> = added
+                .into_iter()
+                .filter(|topic| state.topics.contains_key(topic))
+                .collect();
+            state.unsent.extend(still_added);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+async fn submit_subscriptions<A>(
+    client: &Client<A>,
+    client_id: &str,
+    topics: &[String],
+) -> Result<()> {
+    let url = client.endpoint("realtime");
+    let payload = json!({
+        "clientId": client_id,
+        "subscriptions": topics,
+    });
+    let resp = Httpc::post(client, &url, payload.to_string()).await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!(
+            "Updating realtime subscriptions failed: HTTP {}",
+            resp.status().as_u16()
+        ));
+    }
+    Ok(())
+}
+
+async fn run<A>(shared: Arc<Shared<A>>) {
+    loop {
+        // Errors simply end this connection attempt; the loop reconnects.
+        let _ = listen(&shared).await;
+        shared.state.lock().unwrap().client_id = None;
+        shared.set_connection_state(ConnectionState::Reconnecting);
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn listen<A>(shared: &Shared<A>) -> Result<()> {
+    let url = shared.client.endpoint("realtime");
+    let resp = Httpc::get_stream(&shared.client, &url).await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!("Opening {} failed: HTTP {}", url, resp.status()));
+    }
+
+    let mut body = resp.bytes_stream();
+    let mut buffer = Vec::new();
+    while let Some(chunk) = body.next().await {
+        buffer.extend_from_slice(&chunk?);
+        // Decode only complete events, so characters split across chunks
+        // stay intact.
+        while let Some(end) = event_end(&buffer) {
+            let block: Vec<u8> = buffer.drain(..end).collect();
+            if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                handle_event(shared, event).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The length of the first complete event in `buffer`, including the blank
+/// line (`\n\n` or `\r\n\r\n`) that ends it.
+fn event_end(buffer: &[u8]) -> Option<usize> {
+    let find = |separator: &[u8]| {
+        buffer
+            .windows(separator.len())
+            .position(|window| window == separator)
+            .map(|pos| pos + separator.len())
+    };
+    match (find(b"\n\n"), find(b"\r\n\r\n")) {
+        (Some(lf), Some(crlf)) => Some(lf.min(crlf)),
+        (lf, crlf) => lf.or(crlf),
+    }
+}
+
+async fn handle_event<A>(shared: &Shared<A>, event: RawEvent) -> Result<()> {
+    if event.topic == "PB_CONNECT" {
+        let client_id = serde_json::from_str::<Value>(&event.data)?
+            .get("clientId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("PB_CONNECT event without a clientId"))?;
+        let topics = {
+            let mut state = shared.state.lock().unwrap();
+            state.client_id = Some(client_id.clone());
+            state.dirty = false;
+            state.unsent.clear();
+            state.topics.keys().cloned().collect::<Vec<_>>()
+        };
+        shared.set_connection_state(ConnectionState::Connected);
+        if !topics.is_empty() {
+            submit_subscriptions(&shared.client, &client_id, &topics).await?;
+        }
+        return Ok(());
+    }
+
+    let mut state = shared.state.lock().unwrap();
+    if let Some(listeners) = state.topics.get_mut(&event.topic) {
+        listeners.retain(|listener| {
+            listener
+                .send(Ok(RawEvent {
+                    topic: event.topic.clone(),
+                    data: event.data.clone(),
+                }))
+                .is_ok()
+        });
+        // Every stream for this topic was dropped; unsubscribe it with the
+        // next batched update.
+        if listeners.is_empty() {
+            state.topics.remove(&event.topic);
+            state.unsent.remove(&event.topic);
+            state.dirty = true;
+            shared.changed.notify_one();
+        }
+    }
+    Ok(())
+}
+
+fn parse_event(block: &str) -> Option<RawEvent> {
+    let mut topic = None;
+    let mut data = Vec::new();
+    for line in block.lines() {
+        if let Some(value) = line.strip_prefix("event:") {
+            topic = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+    topic.map(|topic| RawEvent {
+        topic,
+        data: data.join("\n"),
+    })
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// A PocketBase sort expression, built from typed terms so the direction
+/// prefixes are always right.
+///
+/// ```
+/// use pocketbase_client::sort::Sort;
+///
+/// let sort = Sort::asc("created").then(Sort::desc("name"));
+/// assert_eq!(sort.to_string(), "created,-name");
+/// assert_eq!(Sort::random().to_string(), "@random");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sort(String);
+
+impl Sort {
+    /// Sort by `field`, lowest first.
+    pub fn asc(field: &str) -> Self {
+        Sort(field.to_string())
+    }
+
+    /// Sort by `field`, highest first.
+    pub fn desc(field: &str) -> Self {
+        Sort(format!("-{}", field))
+    }
+
+    /// Return items in random order.
+    pub fn random() -> Self {
+        Sort("@random".to_string())
+    }
+
+    /// Break ties with `other`.
+    pub fn then(self, other: Sort) -> Self {
+        Sort(format!("{},{}", self.0, other.0))
+    }
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Sort> for String {
+    fn from(sort: Sort) -> Self {
+        sort.0
+    }
+}
@@ -0,0 +1,135 @@
+use futures::future::BoxFuture;
+use reqwest::{Request, Response};
+use std::fmt;
+use std::sync::Arc;
+
+#[cfg(feature = "test-util")]
+pub use mock::MockTransport;
+
+/// Sends the requests a [`Client`](crate::client::Client) builds. The
+/// client's `reqwest::Client` is used unless another transport is set with
+/// [`Client::with_transport`](crate::client::Client::with_transport).
+///
+/// Auth headers, default headers, retries and timeouts are applied before a
+/// request reaches the transport.
+pub trait Transport: Send + Sync {
+    fn execute(&self, request: Request) -> BoxFuture<'static, reqwest::Result<Response>>;
+}
+
+impl Transport for reqwest::Client {
+    fn execute(&self, request: Request) -> BoxFuture<'static, reqwest::Result<Response>> {
+        Box::pin(reqwest::Client::execute(self, request))
+    }
+}
+
+/// A shared [`Transport`], so clients stay `Clone` and `Debug`.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn Transport>);
+
+impl fmt::Debug for SharedTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+#[cfg(feature = "test-util")]
+mod mock {
+    use super::Transport;
+    use futures::future::BoxFuture;
+    use reqwest::header::CONTENT_TYPE;
+    use reqwest::{Method, Request, Response};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone)]
+    struct MockRoute {
+        method: Method,
+        pattern: String,
+        status: u16,
+        body: Value,
+    }
+
+    impl MockRoute {
+        fn matches(&self, method: &Method, path: &str) -> bool {
+            if self.method != *method {
+                return false;
+            }
+            match self.pattern.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == self.pattern,
+            }
+        }
+    }
+
+    /// A [`Transport`] answering with canned JSON responses instead of
+    /// sending anything, for testing code that uses the client.
+    ///
+    /// Clones share their routes and recorded requests, so keep a clone to
+    /// inspect [`requests`](Self::requests) after handing one to the client.
+    /// Requests no route matches are answered with a PocketBase style 404.
+    #[derive(Debug, Clone, Default)]
+    pub struct MockTransport {
+        routes: Arc<Mutex<Vec<MockRoute>>>,
+        requests: Arc<Mutex<Vec<(Method, String)>>>,
+    }
+
+    impl MockTransport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Answer `method` requests to `pattern` with `status` and `body`.
+        /// The pattern is matched against the URL path, e.g.
+        /// `/api/collections/posts/records`; a trailing `*` matches any path
+        /// starting with the rest. Routes added first take precedence.
+        pub fn respond(self, method: Method, pattern: &str, status: u16, body: Value) -> Self {
+            self.routes.lock().unwrap().push(MockRoute {
+                method,
+                pattern: pattern.to_string(),
+                status,
+                body,
+            });
+            self
+        }
+
+        /// The method and URL of every request received so far, in order.
+        pub fn requests(&self) -> Vec<(Method, String)> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn response(&self, request: &Request) -> (u16, Value) {
+            let path = request.url().path();
+            let routes = self.routes.lock().unwrap();
+            match routes
+                .iter()
+                .find(|route| route.matches(request.method(), path))
+            {
+                Some(route) => (route.status, route.body.clone()),
+                None => (
+                    404,
+                    json!({
+                        "code": 404,
+                        "message": format!("No mock response for {} {}", request.method(), path),
+                        "data": {}
+                    }),
+                ),
+            }
+        }
+    }
+
+    impl Transport for MockTransport {
+        fn execute(&self, request: Request) -> BoxFuture<'static, reqwest::Result<Response>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((request.method().clone(), request.url().to_string()));
+            let (status, body) = self.response(&request);
+            let response = http::Response::builder()
+                .status(status)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.to_string())
+                .expect("mock responses are always valid");
+            Box::pin(futures::future::ready(Ok(Response::from(response))))
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde_json::json;
+
+#[test]
+fn endpoint_joins_without_double_slashes() {
+    let client = Client::new("http://localhost:8090/");
+    assert_eq!(
+        client.endpoint("collections/posts/records"),
+        "http://localhost:8090/api/collections/posts/records"
+    );
+    assert_eq!(
+        client.endpoint("/health"),
+        "http://localhost:8090/api/health"
+    );
+
+    let client = Client::new("https://example.com/pb/").with_api_prefix("/v1/");
+    assert_eq!(
+        client.endpoint("health"),
+        "https://example.com/pb/v1/health"
+    );
+
+    let client = Client::new("https://example.com").with_api_prefix("");
+    assert_eq!(client.endpoint("health"), "https://example.com/health");
+}
+
+#[test]
+fn new_trims_trailing_slash() {
+    let with_slash = Client::new("http://localhost:8090/");
+    let without_slash = Client::new("http://localhost:8090");
+
+    assert_eq!(with_slash.base_url, "http://localhost:8090");
+    assert_eq!(
+        with_slash.endpoint("collections/posts/records"),
+        without_slash.endpoint("collections/posts/records")
+    );
+
+    let custom = Client::with_http_client("http://localhost:8090/", reqwest::Client::new());
+    assert_eq!(custom.base_url, "http://localhost:8090");
+}
+
+#[tokio::test]
+async fn trailing_slash_base_url_reaches_api() {
+    let server = MockServer::start();
+    let view = server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200)
+            .json_body(json!({ "id": "a1", "title": "hello" }));
+    });
+
+    let client = Client::new(&server.url("/"));
+    let record = client
+        .records("posts")
+        .view("a1")
+        .call::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    view.assert();
+    assert_eq!(record["title"], "hello");
+}
+
+#[tokio::test]
+async fn subpath_deployment_with_custom_prefix() {
+    let server = MockServer::start();
+    let health = server.mock(|when, then| {
+        when.method(GET).path("/pocketbase/custom-api/health");
+        then.status(200)
+            .json_body(json!({ "code": 200, "message": "API is healthy." }));
+    });
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/pocketbase/custom-api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1, "perPage": 30, "totalItems": 0, "totalPages": 0, "items": []
+        }));
+    });
+
+    let client = Client::new(&server.url("/pocketbase/")).with_api_prefix("custom-api");
+    client.health_check().await.unwrap();
+    client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    health.assert();
+    list.assert();
+}
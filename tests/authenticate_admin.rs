@@ -1,6 +1,9 @@
 use httpmock::prelude::*;
 use pocketbase_client::admin::Admin;
+use pocketbase_client::client::{AuthError, Client};
+use pocketbase_client::error::CollectionDestroyError;
 use serde_json::json;
+use std::time::Duration;
 
 #[tokio::test]
 pub async fn authenticate_admin_success() {
@@ -20,6 +23,95 @@ pub async fn authenticate_admin_failure() {
     assert!(client.is_err());
 }
 
+#[tokio::test]
+pub async fn admin_token_refreshes_through_admins_endpoint() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/admins/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "admin-token",
+            "admin": { "id": "b6e4b08274f34e9" }
+        }));
+    });
+    let refresh = server.mock(|when, then| {
+        when.method(POST).path("/api/admins/auth-refresh");
+        then.status(200).json_body(json!({
+            "token": "refreshed-token",
+            "admin": { "id": "b6e4b08274f34e9" }
+        }));
+    });
+
+    let admin = Admin::new(server.base_url().as_str())
+        .auth_with_password("admin@example.com", "secret")
+        .await
+        .unwrap();
+    assert!(admin.state.superuser);
+    assert_eq!(admin.record_id(), Some("b6e4b08274f34e9"));
+
+    let refreshed = admin.auth_refresh().await.unwrap();
+    refreshed.auth_refresh().await.unwrap();
+    refresh.assert_hits(2);
+    assert_eq!(refreshed.record_id(), Some("b6e4b08274f34e9"));
+}
+
+#[tokio::test]
+pub async fn auth_as_admin_tags_superuser() {
+    let server = MockServer::start();
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/_superusers/auth-with-password")
+            .json_body(json!({
+                "identity": "admin@example.com",
+                "password": "secret"
+            }));
+        then.status(200).json_body(json!({
+            "token": "superuser-token",
+            "record": { "id": "sup3rus3r", "email": "admin@example.com" }
+        }));
+    });
+    let destroy = server.mock(|when, then| {
+        when.method(DELETE).path("/api/collections/articles");
+        then.status(204);
+    });
+
+    let admin = Client::new(server.base_url().as_str())
+        .auth_as_admin("admin@example.com", "secret")
+        .await
+        .unwrap();
+    login.assert();
+    assert!(admin.state.superuser);
+    assert_eq!(admin.state.collection, "_superusers");
+    admin.collections().delete("articles").call().await.unwrap();
+    destroy.assert();
+}
+
+#[tokio::test]
+pub async fn collection_delete_requires_superuser() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "user-token",
+            "record": { "id": "us3r", "email": "user@example.com" }
+        }));
+    });
+    let destroy = server.mock(|when, then| {
+        when.method(DELETE).path("/api/collections/articles");
+        then.status(204);
+    });
+
+    let user = Client::new(server.base_url().as_str())
+        .auth_with_password("users", "user@example.com", "secret")
+        .await
+        .unwrap();
+    assert!(!user.state.superuser);
+    let result = user.collections().delete("articles").call().await;
+
+    assert!(matches!(result, Err(CollectionDestroyError::NotSuperuser)));
+    destroy.assert_hits(0);
+}
+
 fn mock_admin_login() -> MockServer {
     let server = MockServer::start();
     server.mock(|when, then| {
@@ -68,3 +160,138 @@ fn mock_admin_login() -> MockServer {
 
     server
 }
+
+#[tokio::test]
+pub async fn impersonate_returns_user_client() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/_superusers/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "superuser-token",
+            "record": { "id": "sup3rus3r", "email": "admin@example.com" }
+        }));
+    });
+    let impersonate = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/impersonate/us3r")
+            .header("Authorization", "superuser-token")
+            .json_body(json!({ "duration": 3600 }));
+        then.status(200).json_body(json!({
+            "token": "impersonated-token",
+            "record": { "id": "us3r", "email": "user@example.com" }
+        }));
+    });
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .header("Authorization", "impersonated-token");
+        then.status(200).json_body(json!({
+            "page": 1, "perPage": 30, "totalItems": 0, "totalPages": 0, "items": []
+        }));
+    });
+
+    let admin = Client::new(server.base_url().as_str())
+        .auth_as_admin("admin@example.com", "secret")
+        .await
+        .unwrap();
+    let user = admin
+        .impersonate("users", "us3r", Duration::from_secs(3600))
+        .await
+        .unwrap();
+    impersonate.assert();
+    assert_eq!(user.record_id(), Some("us3r"));
+    assert_eq!(user.state.collection, "users");
+    assert!(!user.state.superuser);
+
+    user.records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await
+        .unwrap();
+    list.assert();
+}
+
+#[tokio::test]
+pub async fn impersonated_client_does_not_refresh_on_unauthorized() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/_superusers/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "superuser-token",
+            "record": { "id": "sup3rus3r" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/impersonate/us3r");
+        then.status(200).json_body(json!({
+            "token": "impersonated-token",
+            "record": { "id": "us3r" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/p1");
+        then.status(401).json_body(json!({
+            "code": 401,
+            "message": "The request requires valid record authorization token.",
+            "data": {}
+        }));
+    });
+    let refresh = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-refresh");
+        then.status(200)
+            .json_body(json!({ "token": "refreshed-token" }));
+    });
+
+    let admin = Client::new(server.base_url().as_str())
+        .auth_as_admin("admin@example.com", "secret")
+        .await
+        .unwrap();
+    let user = admin
+        .impersonate("users", "us3r", Duration::from_secs(60))
+        .await
+        .unwrap();
+    let result = user
+        .records("posts")
+        .view("p1")
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(result.is_err());
+    refresh.assert_hits(0);
+}
+
+#[tokio::test]
+pub async fn impersonate_reports_forbidden() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "user-token",
+            "record": { "id": "us3r" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/impersonate/other");
+        then.status(403).json_body(json!({
+            "code": 403,
+            "message": "The authorized record is not allowed to perform this action.",
+            "data": {}
+        }));
+    });
+
+    let user = Client::new(server.base_url().as_str())
+        .auth_with_password("users", "user@example.com", "secret")
+        .await
+        .unwrap();
+    let result = user
+        .impersonate("users", "other", Duration::from_secs(0))
+        .await;
+
+    assert!(matches!(result, Err(AuthError::Validation(err)) if err.status == 403));
+}
@@ -0,0 +1,117 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde_json::json;
+
+#[tokio::test]
+pub async fn list_auth_methods_success() {
+    let mockserver = mock_oauth2();
+    let methods = Client::new(mockserver.base_url().as_str())
+        .list_auth_methods("users")
+        .await
+        .unwrap();
+
+    assert!(methods.email_password);
+    assert!(!methods.username_password);
+    assert_eq!(methods.auth_providers.len(), 1);
+    let github = &methods.auth_providers[0];
+    assert_eq!(github.name, "github");
+    assert_eq!(github.code_verifier, "verifier-123");
+    assert_eq!(github.code_challenge_method, "S256");
+}
+
+#[tokio::test]
+pub async fn authenticate_oauth2_success() {
+    let mockserver = mock_oauth2();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_oauth2(
+            "users",
+            "github",
+            "provider-code",
+            "verifier-123",
+            "http://localhost:8090/redirect",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(client.auth_token().as_deref(), Some("oauth2-token"));
+    assert_eq!(client.state.collection, "users");
+    assert_eq!(client.record_id(), Some("8171022dc95a4ed"));
+}
+
+#[tokio::test]
+pub async fn authenticate_oauth2_error() {
+    let mockserver = mock_oauth2();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_oauth2(
+            "users",
+            "github",
+            "expired-code",
+            "verifier-123",
+            "http://localhost:8090/redirect",
+        )
+        .await;
+    assert!(client.is_err());
+}
+
+fn mock_oauth2() -> MockServer {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/users/auth-methods");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "usernamePassword": false,
+                "emailPassword": true,
+                "authProviders": [
+                    {
+                        "name": "github",
+                        "displayName": "GitHub",
+                        "state": "state-abc",
+                        "authUrl": "https://github.com/login/oauth/authorize?client_id=demo&state=state-abc",
+                        "codeVerifier": "verifier-123",
+                        "codeChallenge": "challenge-xyz",
+                        "codeChallengeMethod": "S256"
+                    }
+                ]
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-oauth2")
+            .json_body(json!({
+                "provider": "github",
+                "code": "provider-code",
+                "codeVerifier": "verifier-123",
+                "redirectUrl": "http://localhost:8090/redirect"
+            }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "token": "oauth2-token",
+                "record": {
+                    "id": "8171022dc95a4ed",
+                    "collectionId": "d2972397d45614e",
+                    "collectionName": "users",
+                    "email": "test@example.com"
+                },
+                "meta": {
+                    "id": "gh-42",
+                    "name": "Test User"
+                }
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-oauth2")
+            .body_contains("expired-code");
+        then.status(400)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 400,
+                "message": "Failed to authenticate.",
+                "data": {}
+            }));
+    });
+
+    server
+}
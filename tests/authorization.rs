@@ -0,0 +1,67 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde_json::{json, Value};
+
+const TOKEN: &str = "test-token";
+
+#[tokio::test]
+async fn every_verb_sends_authorization() {
+    let server = MockServer::start();
+    let get = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records/a1")
+            .header("Authorization", TOKEN);
+        then.status(200).json_body(json!({ "id": "a1" }));
+    });
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .header("Authorization", TOKEN);
+        then.status(200).json_body(json!({
+            "id": "a1",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-25 11:03:35.163Z"
+        }));
+    });
+    let patch = server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1")
+            .header("Authorization", TOKEN);
+        then.status(200).json_body(json!({ "id": "a1" }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/a1")
+            .header("Authorization", TOKEN);
+        then.status(204);
+    });
+
+    let client = Client::from_token(server.base_url().as_str(), "users", TOKEN, false)
+        .await
+        .unwrap();
+    let posts = client.records("posts");
+
+    posts.view("a1").call::<Value>().await.unwrap();
+    posts
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap();
+    posts
+        .create(json!({ "title": "hello" }))
+        .file_from_bytes("attachment", "hello.txt", b"hello".to_vec())
+        .call()
+        .await
+        .unwrap();
+    posts
+        .update("a1", json!({ "title": "edited" }))
+        .call()
+        .await
+        .unwrap();
+    posts.destroy("a1").call().await.unwrap();
+
+    get.assert();
+    post.assert_hits(2);
+    patch.assert();
+    delete.assert();
+}
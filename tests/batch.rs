@@ -0,0 +1,93 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Clone, Debug, Serialize)]
+struct Post {
+    title: String,
+}
+
+#[tokio::test]
+async fn batch_sends_all_operations_in_one_request() {
+    let server = MockServer::start();
+    let batch_mock = server.mock(|when, then| {
+        when.method(POST).path("/api/batch").json_body(json!({
+            "requests": [
+                {
+                    "method": "POST",
+                    "url": "/api/collections/posts/records",
+                    "body": { "title": "first" }
+                },
+                {
+                    "method": "PATCH",
+                    "url": "/api/collections/posts/records/a1",
+                    "body": { "title": "edited" }
+                },
+                {
+                    "method": "DELETE",
+                    "url": "/api/collections/comments/records/c1"
+                }
+            ]
+        }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!([
+                { "status": 200, "body": { "id": "n3w", "title": "first" } },
+                { "status": 200, "body": { "id": "a1", "title": "edited" } },
+                { "status": 204, "body": null }
+            ]));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let results = client
+        .batch()
+        .create(
+            "posts",
+            Post {
+                title: "first".to_string(),
+            },
+        )
+        .unwrap()
+        .update("posts", "a1", json!({ "title": "edited" }))
+        .unwrap()
+        .delete("comments", "c1")
+        .call()
+        .await
+        .unwrap();
+
+    batch_mock.assert();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].status, 200);
+    assert_eq!(results[0].body["id"], "n3w");
+    assert_eq!(results[2].status, 204);
+}
+
+#[tokio::test]
+async fn batch_failure_is_an_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/batch");
+        then.status(400)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 400,
+                "message": "Batch transaction failed.",
+                "data": {
+                    "requests": {
+                        "0": {
+                            "code": "batch_request_failed",
+                            "message": "Batch request failed."
+                        }
+                    }
+                }
+            }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let result = client.batch().delete("posts", "missing").call().await;
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("HTTP 400"));
+    assert!(message.contains("Batch transaction failed."));
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "blocking")]
+
+use httpmock::prelude::*;
+use pocketbase_client::blocking::BlockingClient;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Post {
+    id: String,
+    title: String,
+}
+
+#[test]
+fn blocking_client_lists_and_views_records() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "title != ''")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 1,
+            "totalItems": 2,
+            "totalPages": 2,
+            "items": [{ "id": "b2", "title": "second" }]
+        }));
+    });
+    let view = server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200)
+            .json_body(json!({ "id": "a1", "title": "first" }));
+    });
+
+    let client = BlockingClient::new(server.base_url().as_str()).unwrap();
+    let page = client
+        .records("posts")
+        .list()
+        .filter("title != ''")
+        .page(2)
+        .per_page(1)
+        .call::<Post>()
+        .unwrap();
+    let post = client.records("posts").view("a1").call::<Post>().unwrap();
+
+    list.assert();
+    view.assert();
+    assert_eq!(page.items[0].title, "second");
+    assert_eq!(post.title, "first");
+}
+
+#[test]
+fn blocking_client_authenticates_and_writes() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-password");
+        then.status(200).json_body(json!({
+            "token": "user-token",
+            "record": { "id": "us3r" }
+        }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .header("Authorization", "user-token")
+            .json_body(json!({ "id": "", "title": "new" }));
+        then.status(200).json_body(json!({
+            "id": "c3",
+            "collectionId": "pbc_1125843985",
+            "collectionName": "posts",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-25 11:03:35.163Z"
+        }));
+    });
+    let destroy = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/c3")
+            .header("Authorization", "user-token");
+        then.status(204);
+    });
+
+    let client = BlockingClient::new(server.base_url().as_str())
+        .unwrap()
+        .auth_with_password("users", "user@example.com", "secret")
+        .unwrap();
+    let created = client
+        .records("posts")
+        .create(Post {
+            title: "new".to_string(),
+            ..Default::default()
+        })
+        .call()
+        .unwrap();
+    client.records("posts").destroy("c3").call().unwrap();
+
+    assert_eq!(created.id, "c3");
+    create.assert();
+    destroy.assert();
+    assert_eq!(client.client().record_id(), Some("us3r"));
+}
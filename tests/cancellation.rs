@@ -0,0 +1,101 @@
+#![cfg(feature = "cancellation")]
+
+use futures::StreamExt;
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::error::CancelledError;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+fn mock_page<'a>(server: &'a MockServer, page: i32, ids: [&str; 2]) -> httpmock::Mock<'a> {
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", page.to_string());
+        then.status(200).json_body(json!({
+            "page": page,
+            "perPage": 2,
+            "totalItems": 4,
+            "totalPages": 2,
+            "items": [{ "id": ids[0] }, { "id": ids[1] }]
+        }));
+    })
+}
+
+#[tokio::test]
+async fn get_all_stops_between_pages_when_cancelled() {
+    let server = MockServer::start();
+    let first = mock_page(&server, 1, ["a1", "b2"]);
+    let second = mock_page(&server, 2, ["c3", "d4"]);
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(2);
+    let token = CancellationToken::new();
+    let err = client
+        .records("posts")
+        .list()
+        .cancel_on(token.clone())
+        .get_all_with_progress::<Value>(|_, _| token.cancel())
+        .await
+        .unwrap_err();
+
+    let cancelled = err.downcast_ref::<CancelledError>().unwrap();
+    assert_eq!(cancelled.fetched, 2);
+    first.assert();
+    second.assert_hits(0);
+}
+
+#[tokio::test]
+async fn stream_ends_with_cancelled_error() {
+    let server = MockServer::start();
+    mock_page(&server, 1, ["a1", "b2"]);
+    let second = mock_page(&server, 2, ["c3", "d4"]);
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(2);
+    let token = CancellationToken::new();
+    let list = client
+        .records("posts")
+        .list()
+        .per_page(2)
+        .cancel_on(token.clone());
+    let mut records = Box::pin(list.stream::<Value>());
+
+    assert_eq!(records.next().await.unwrap().unwrap()["id"], "a1");
+    token.cancel();
+    assert_eq!(records.next().await.unwrap().unwrap()["id"], "b2");
+    let err = records.next().await.unwrap().unwrap_err();
+    assert_eq!(err.downcast_ref::<CancelledError>().unwrap().fetched, 2);
+    assert!(records.next().await.is_none());
+    second.assert_hits(0);
+}
+
+#[tokio::test]
+async fn cancelling_abandons_in_flight_page() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200)
+            .delay(Duration::from_secs(10))
+            .json_body(json!({ "page": 1, "perPage": 2, "totalItems": 0, "items": [] }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        canceller.cancel();
+    });
+
+    let started = Instant::now();
+    let err = client
+        .records("posts")
+        .list()
+        .cancel_on(token)
+        .get_all::<Value>()
+        .await
+        .unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(err.downcast_ref::<CancelledError>().unwrap().fetched, 0);
+}
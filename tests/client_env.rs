@@ -0,0 +1,100 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::{Client, EnvClient};
+use serde_json::json;
+use std::env;
+use tokio::sync::Mutex;
+
+// Environment variables are process-wide, so tests touching them run one at a time.
+static ENV_LOCK: Mutex<()> = Mutex::const_new(());
+
+const VARS: [&str; 5] = [
+    "POCKETBASE_URL",
+    "POCKETBASE_TOKEN",
+    "POCKETBASE_IDENTITY",
+    "POCKETBASE_PASSWORD",
+    "POCKETBASE_COLLECTION",
+];
+
+fn clear_env() {
+    for var in VARS {
+        env::remove_var(var);
+    }
+}
+
+#[tokio::test]
+async fn from_env_missing_url() {
+    let _guard = ENV_LOCK.lock().await;
+    clear_env();
+
+    let err = Client::from_env().await.unwrap_err();
+    assert!(err.to_string().contains("POCKETBASE_URL"));
+}
+
+#[tokio::test]
+async fn from_env_without_credentials() {
+    let _guard = ENV_LOCK.lock().await;
+    clear_env();
+    env::set_var("POCKETBASE_URL", "http://localhost:8090");
+
+    match Client::from_env().await.unwrap() {
+        EnvClient::NoAuth(client) => assert_eq!(client.base_url, "http://localhost:8090"),
+        EnvClient::Auth(_) => panic!("expected an unauthenticated client"),
+    }
+}
+
+#[tokio::test]
+async fn from_env_with_token() {
+    let _guard = ENV_LOCK.lock().await;
+    clear_env();
+    env::set_var("POCKETBASE_URL", "http://localhost:8090");
+    env::set_var("POCKETBASE_TOKEN", "stored-token");
+
+    match Client::from_env().await.unwrap() {
+        EnvClient::Auth(client) => assert_eq!(client.auth_token().as_deref(), Some("stored-token")),
+        EnvClient::NoAuth(_) => panic!("expected an authenticated client"),
+    }
+}
+
+#[tokio::test]
+async fn from_env_identity_without_password() {
+    let _guard = ENV_LOCK.lock().await;
+    clear_env();
+    env::set_var("POCKETBASE_URL", "http://localhost:8090");
+    env::set_var("POCKETBASE_IDENTITY", "test@example.com");
+
+    let err = Client::from_env().await.unwrap_err();
+    assert!(err.to_string().contains("POCKETBASE_PASSWORD"));
+}
+
+#[tokio::test]
+async fn from_env_with_credentials() {
+    let _guard = ENV_LOCK.lock().await;
+    clear_env();
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body(json!({
+                "identity": "test@example.com",
+                "password": "secret"
+            }))
+            .path("/api/collections/members/auth-with-password");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "token": "members-token",
+                "record": { "id": "m3mb3r1d", "collectionName": "members" }
+            }));
+    });
+    env::set_var("POCKETBASE_URL", server.base_url());
+    env::set_var("POCKETBASE_IDENTITY", "test@example.com");
+    env::set_var("POCKETBASE_PASSWORD", "secret");
+    env::set_var("POCKETBASE_COLLECTION", "members");
+
+    match Client::from_env().await.unwrap() {
+        EnvClient::Auth(client) => {
+            assert_eq!(client.auth_token().as_deref(), Some("members-token"));
+            assert_eq!(client.record_id(), Some("m3mb3r1d"));
+        }
+        EnvClient::NoAuth(_) => panic!("expected an authenticated client"),
+    }
+}
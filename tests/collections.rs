@@ -1,5 +1,8 @@
 use httpmock::prelude::*;
 use pocketbase_client::admin::Admin;
+use pocketbase_client::client::Client;
+use pocketbase_client::collections::{Collection, CollectionList, FieldDeclaration, FieldType};
+use pocketbase_client::error::CollectionDestroyError;
 use serde_json::json;
 
 #[tokio::test]
@@ -14,6 +17,150 @@ async fn collections_list_success() {
     assert!(collections_list.is_ok())
 }
 
+#[tokio::test]
+async fn collections_list_skip_total() {
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections")
+            .query_param("skipTotal", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": -1,
+            "items": [articles_collection()]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let collections = client
+        .collections()
+        .list()
+        .skip_total(true)
+        .call()
+        .await
+        .unwrap();
+
+    list_mock.assert();
+    assert_eq!(collections.items[0].name, "articles");
+    assert_eq!(collections.total_pages, -1);
+    assert!(!collections.has_next_page());
+}
+
+#[test]
+fn collection_list_without_total_items() {
+    let list: CollectionList = serde_json::from_value(json!({
+        "page": 1,
+        "perPage": 30,
+        "items": []
+    }))
+    .unwrap();
+
+    assert_eq!(list.total_items, -1);
+    assert_eq!(list.total_pages, -1);
+}
+
+#[tokio::test]
+async fn collections_list_sends_camel_case_paging() {
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections")
+            .query_param("perPage", "20")
+            .query_param("page", "3")
+            .matches(|req| {
+                !req.query_params
+                    .as_ref()
+                    .is_some_and(|params| params.iter().any(|(key, _)| key == "per_page"))
+            });
+        then.status(200).json_body(json!({
+            "page": 3,
+            "perPage": 20,
+            "totalItems": 41,
+            "items": [articles_collection()]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let collections = client
+        .collections()
+        .list()
+        .per_page(20)
+        .page(3)
+        .call()
+        .await
+        .unwrap();
+
+    list_mock.assert();
+    assert_eq!(collections.per_page, 20);
+}
+
+#[tokio::test]
+async fn collections_call_with_meta_exposes_headers() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections");
+        then.status(200)
+            .header("X-RateLimit-Remaining", "41")
+            .json_body(json!({
+                "page": 1,
+                "perPage": 100,
+                "totalItems": 1,
+                "items": [articles_collection()]
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/articles");
+        then.status(200)
+            .header("X-RateLimit-Remaining", "40")
+            .json_body(articles_collection());
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let (meta, collections) = client.collections().list().call_with_meta().await.unwrap();
+    assert_eq!(meta.status, 200);
+    assert_eq!(meta.header("x-ratelimit-remaining"), Some("41"));
+    assert_eq!(collections.items[0].name, "articles");
+
+    let (meta, collection) = client
+        .collections()
+        .view("articles")
+        .call_with_meta()
+        .await
+        .unwrap();
+    assert_eq!(meta.header("X-RateLimit-Remaining"), Some("40"));
+    assert_eq!(collection.name, "articles");
+}
+
+#[tokio::test]
+async fn collection_list_sends_extra_query_params() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections")
+            .query_param("perPage", "100")
+            .query_param("system", "false");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "totalPages": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let collections = client
+        .collections()
+        .list()
+        .query_param("system", "false")
+        .call()
+        .await
+        .unwrap();
+    assert!(collections.items.is_empty());
+    list.assert();
+}
+
 #[tokio::test]
 async fn colletion_view_succes() {
     let mockserver_url = mockserver().base_url();
@@ -25,8 +172,382 @@ async fn colletion_view_succes() {
     assert!(collection.is_ok())
 }
 
+#[tokio::test]
+async fn collection_create_then_view_success() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let created = admin_client
+        .collections()
+        .create("articles")
+        .schema(vec![FieldDeclaration {
+            name: "title",
+            r#type: FieldType::Text,
+            required: true,
+            ..Default::default()
+        }])
+        .list_rule("")
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(created.name, "articles");
+
+    let viewed = admin_client
+        .collections()
+        .view("articles")
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(viewed.id, created.id);
+    assert_eq!(viewed.schema[0].name, "title");
+}
+
+#[tokio::test]
+async fn collection_create_with_autodate_fields() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let builder = admin_client
+        .collections()
+        .create("articles")
+        .created_autodate()
+        .updated_autodate();
+    let schema = serde_json::to_value(builder.collection_details.unwrap().schema).unwrap();
+
+    assert_eq!(
+        schema,
+        json!([
+            {
+                "name": "created",
+                "type": "autodate",
+                "required": false,
+                "onCreate": true,
+                "onUpdate": false
+            },
+            {
+                "name": "updated",
+                "type": "autodate",
+                "required": false,
+                "onCreate": true,
+                "onUpdate": true
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn collection_create_without_details_fails() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let created = admin_client.collections().create("articles").call().await;
+    assert!(created.is_err());
+}
+
+#[tokio::test]
+async fn collection_create_failure_truncates_non_ascii_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/admins/auth-with-password");
+        then.status(200)
+            .json_body(json!({ "token": "admin-token" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let admin_client = Admin::new(server.base_url().as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+    let err = admin_client
+        .collections()
+        .create("articles")
+        .list_rule("")
+        .call()
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().ends_with(&"€".repeat(666)));
+}
+
+#[tokio::test]
+async fn collection_delete_success() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let deleted = admin_client.collections().delete("articles").call().await;
+    assert!(deleted.is_ok());
+}
+
+#[tokio::test]
+async fn collection_delete_not_found() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let deleted = admin_client.collections().delete("missing").call().await;
+    assert!(matches!(
+        deleted,
+        Err(CollectionDestroyError::NotFound { name }) if name == "missing"
+    ));
+}
+
+#[tokio::test]
+async fn collection_delete_failure_truncates_non_ascii_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/admins/auth-with-password");
+        then.status(200)
+            .json_body(json!({ "token": "admin-token" }));
+    });
+    server.mock(|when, then| {
+        when.method(DELETE).path("/api/collections/articles");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let admin_client = Admin::new(server.base_url().as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+    let deleted = admin_client.collections().delete("articles").call().await;
+
+    assert!(matches!(
+        deleted,
+        Err(CollectionDestroyError::Http { status: 500, body_snippet, .. })
+            if body_snippet == "€".repeat(666)
+    ));
+}
+
+#[tokio::test]
+async fn collection_update_failure_truncates_non_ascii_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/admins/auth-with-password");
+        then.status(200)
+            .json_body(json!({ "token": "admin-token" }));
+    });
+    server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/articles");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let admin_client = Admin::new(server.base_url().as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+    let err = admin_client
+        .collections()
+        .update("articles")
+        .list_rule(Some(""))
+        .call()
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().ends_with(&"€".repeat(666)));
+}
+
+#[tokio::test]
+async fn collection_update_adds_field_and_keeps_other_rules() {
+    let mockserver_url = mockserver().base_url();
+    let admin_client = Admin::new(mockserver_url.as_str())
+        .auth_with_password("", "")
+        .await
+        .unwrap();
+
+    let updated = admin_client
+        .collections()
+        .update("articles")
+        .add_field(FieldDeclaration {
+            name: "body",
+            r#type: FieldType::Editor,
+            required: false,
+            ..Default::default()
+        })
+        .delete_rule(None)
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(updated.schema.len(), 2);
+}
+
+#[test]
+fn collection_rules_classify_public_and_admin_only() {
+    let articles: Collection = serde_json::from_value(articles_collection()).unwrap();
+    assert!(!articles.system);
+    assert_eq!(articles.list_rule.as_deref(), Some(""));
+    assert!(articles.is_publicly_listable());
+    assert!(!articles.is_publicly_viewable());
+    assert!(!articles.is_admin_only());
+
+    let mut locked = articles_collection();
+    locked["listRule"] = json!(null);
+    let locked: Collection = serde_json::from_value(locked).unwrap();
+    assert!(!locked.is_publicly_listable());
+    assert!(locked.is_admin_only());
+}
+
+#[test]
+fn collection_rules_keep_null_apart_from_empty() {
+    let mut mixed = articles_collection();
+    mixed["viewRule"] = json!("");
+    mixed["createRule"] = json!("@request.auth.id != ''");
+    mixed["deleteRule"] = json!(null);
+    let mixed: Collection = serde_json::from_value(mixed).unwrap();
+
+    assert_eq!(mixed.list_rule.as_deref(), Some(""));
+    assert_eq!(mixed.view_rule.as_deref(), Some(""));
+    assert_eq!(mixed.create_rule.as_deref(), Some("@request.auth.id != ''"));
+    assert_eq!(mixed.update_rule, None);
+    assert_eq!(mixed.delete_rule, None);
+}
+
+#[test]
+fn field_types_parse_known_and_unknown_kinds() {
+    let mut articles = articles_collection();
+    articles["schema"]
+        .as_array_mut()
+        .unwrap()
+        .extend([
+            json!({ "system": false, "id": "r1", "name": "author", "type": "relation", "required": false, "unique": false }),
+            json!({ "system": false, "id": "g1", "name": "place", "type": "geoPoint", "required": false, "unique": false }),
+            json!({ "system": false, "id": "v1", "name": "embedding", "type": "vector", "required": false, "unique": false }),
+        ]);
+    let articles: Collection = serde_json::from_value(articles).unwrap();
+
+    let types: Vec<_> = articles.schema.iter().map(|f| f.r#type.clone()).collect();
+    assert_eq!(
+        types,
+        [
+            FieldType::Text,
+            FieldType::Relation,
+            FieldType::GeoPoint,
+            FieldType::Unknown("vector".to_string()),
+        ]
+    );
+
+    let round_trip = serde_json::to_value(&articles.schema[3]).unwrap();
+    assert_eq!(round_trip["type"], "vector");
+    assert_eq!(
+        serde_json::to_value(FieldType::GeoPoint).unwrap(),
+        "geoPoint"
+    );
+}
+
+fn articles_collection() -> serde_json::Value {
+    json!({
+        "id": "a1b2c3d4e5f6a7b",
+        "created": "2022-06-22 07:13:00.643Z",
+        "updated": "2022-06-22 07:13:00.643Z",
+        "name": "articles",
+        "type": "base",
+        "system": false,
+        "schema": [
+            {
+                "system": false,
+                "id": "kx9q2lmn",
+                "name": "title",
+                "type": "text",
+                "required": true,
+                "unique": false,
+                "options": {}
+            }
+        ],
+        "listRule": "",
+        "viewRule": null,
+        "createRule": null,
+        "updateRule": null,
+        "deleteRule": null,
+        "options": {},
+        "indexes": []
+    })
+}
+
 fn mockserver() -> MockServer {
     let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections")
+            .json_body_partial(
+                r#"{
+                    "name": "articles",
+                    "type": "base",
+                    "schema": [{ "name": "title", "type": "text", "required": true }],
+                    "listRule": ""
+                }"#,
+            );
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(articles_collection());
+    });
+    server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/articles")
+            .json_body(json!({
+                "deleteRule": null,
+                "schema": [
+                    {
+                        "system": false,
+                        "id": "kx9q2lmn",
+                        "name": "title",
+                        "type": "text",
+                        "required": true,
+                        "unique": false,
+                        "options": {}
+                    },
+                    { "name": "body", "type": "editor", "required": false }
+                ]
+            }));
+        let mut collection = articles_collection();
+        collection["schema"].as_array_mut().unwrap().push(json!({
+            "system": false,
+            "id": "p0w8zt3r",
+            "name": "body",
+            "type": "editor",
+            "required": false,
+            "unique": false,
+            "options": {}
+        }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(collection);
+    });
+    server.mock(|when, then| {
+        when.method(DELETE).path("/api/collections/articles");
+        then.status(204);
+    });
+    server.mock(|when, then| {
+        when.method(DELETE).path("/api/collections/missing");
+        then.status(404)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 404,
+                "message": "The requested resource wasn't found.",
+                "data": {}
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/articles");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(articles_collection());
+    });
     server.mock(|when, then| {
         when.method(GET)
         .path("/api/collections/posts")
@@ -184,3 +705,38 @@ fn mockserver() -> MockServer {
 
     server
 }
+
+#[tokio::test]
+async fn collection_get_all_pages_to_completion() {
+    let server = MockServer::start();
+    let pages: Vec<_> = [(1, "c1"), (2, "c2")]
+        .into_iter()
+        .map(|(page, id)| {
+            let mut collection = articles_collection();
+            collection["id"] = json!(id);
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path("/api/collections")
+                    .query_param("sort", "id")
+                    .query_param("perPage", "1")
+                    .query_param("page", page.to_string());
+                then.status(200).json_body(json!({
+                    "page": page,
+                    "perPage": 1,
+                    "totalItems": 2,
+                    "totalPages": 2,
+                    "items": [collection]
+                }));
+            })
+        })
+        .collect();
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(1);
+    let collections = client.collections().get_all().await.unwrap();
+
+    let ids: Vec<_> = collections.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, ["c1", "c2"]);
+    for page in &pages {
+        page.assert();
+    }
+}
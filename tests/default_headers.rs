@@ -0,0 +1,63 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde_json::{json, Value};
+
+fn tenant_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Tenant", HeaderValue::from_static("acme"));
+    headers.insert("Accept-Language", HeaderValue::from_static("de"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+    headers
+}
+
+#[tokio::test]
+async fn default_headers_reach_the_server() {
+    let server = MockServer::start();
+    let view = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records/a1")
+            .header("X-Tenant", "acme")
+            .header("Accept-Language", "de");
+        then.status(200).json_body(json!({ "id": "a1" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_default_headers(tenant_headers());
+    client
+        .records("posts")
+        .view("a1")
+        .call::<Value>()
+        .await
+        .unwrap();
+
+    view.assert();
+}
+
+#[tokio::test]
+async fn request_headers_take_precedence() {
+    let server = MockServer::start();
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .header("X-Tenant", "acme")
+            .header("Content-Type", "application/json")
+            .json_body(json!({ "title": "hello" }));
+        then.status(200).json_body(json!({
+            "id": "a1",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-25 11:03:35.163Z"
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str())
+        .with_public_writes()
+        .with_default_headers(tenant_headers());
+    client
+        .records("posts")
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap();
+
+    create.assert();
+}
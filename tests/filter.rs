@@ -0,0 +1,167 @@
+use chrono::{TimeZone, Utc};
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::filter::{quote, Filter};
+use serde_json::{json, Value};
+
+#[test]
+fn filter_renders_pocketbase_syntax() {
+    let filter = Filter::eq("status", "active")
+        .unwrap()
+        .and(Filter::gt("age", 18).unwrap())
+        .or(Filter::eq("verified", true)
+            .unwrap()
+            .and(Filter::ne("deleted", None::<&str>).unwrap()));
+    assert_eq!(
+        filter.to_string(),
+        "((status = 'active' && age > 18) || (verified = true && deleted != null))"
+    );
+    assert_eq!(
+        Filter::like("title", "50%").unwrap().to_string(),
+        "title ~ '50%'"
+    );
+}
+
+#[test]
+fn filter_escapes_string_values() {
+    let name = String::from("O'Brien' || id != '");
+    assert_eq!(
+        Filter::eq("name", &name).unwrap().to_string(),
+        r"name = 'O\'Brien\' || id != \''"
+    );
+    assert_eq!(
+        Filter::eq("path", r"C:\tmp").unwrap().to_string(),
+        r"path = 'C:\tmp'"
+    );
+}
+
+#[test]
+fn comparisons_reject_values_that_would_escape_their_quote() {
+    let err = Filter::eq("name", r"x\").unwrap_err();
+    assert_eq!(err.value, r"x\");
+    assert!(Filter::like("path", String::from(r"C:\")).is_err());
+    assert!(Filter::ne("owner", Some(r"\")).is_err());
+    assert!(Filter::email(r"a@b.c\").is_err());
+}
+
+#[test]
+fn quote_escapes_only_quotes() {
+    assert_eq!(quote("plain").unwrap(), "'plain'");
+    assert_eq!(quote("' || id != '").unwrap(), r"'\' || id != \''");
+    assert_eq!(quote(r"a\b").unwrap(), r"'a\b'");
+    assert_eq!(quote(r"\'").unwrap(), r"'\\''");
+    assert_eq!(quote("two\nlines").unwrap(), "'two\nlines'");
+}
+
+#[test]
+fn quote_rejects_trailing_backslash() {
+    for value in [r"x\", r"x\\", r"\"] {
+        let err = quote(value).unwrap_err();
+        assert_eq!(err.value, value);
+    }
+}
+
+#[tokio::test]
+async fn quoted_values_reach_the_server_unchanged() {
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", r"path = 'C:\tmp' && name = 'O\'Brien'");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let filter = format!(
+        "path = {} && name = {}",
+        quote(r"C:\tmp").unwrap(),
+        quote("O'Brien").unwrap()
+    );
+    client
+        .records("posts")
+        .list()
+        .filter(filter)
+        .call::<Value>()
+        .await
+        .unwrap();
+
+    list_mock.assert();
+}
+
+#[test]
+fn with_params_substitutes_escaped_values() {
+    let name = "O'Brien";
+    let created = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let filter = Filter::with_params(
+        "name = {:name} && note ~ {:note} && created >= {:created} && age > {:age}",
+        &[
+            ("name", &name),
+            ("note", &"line one\nline {:name}"),
+            ("created", &created),
+            ("age", &18),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        filter.to_string(),
+        "name = 'O\\'Brien' && note ~ 'line one\nline {:name}' \
+         && created >= '2024-01-02 03:04:05.000Z' && age > 18"
+    );
+}
+
+#[test]
+fn with_params_leaves_unknown_placeholders() {
+    let filter = Filter::with_params("a = {:a} && b = {:missing} && c = {:", &[("a", &1)]).unwrap();
+    assert_eq!(filter.to_string(), "a = 1 && b = {:missing} && c = {:");
+}
+
+#[test]
+fn with_params_rejects_values_that_would_escape_their_quote() {
+    let err = Filter::with_params(
+        "name = {:a} && owner = {:b}",
+        &[("a", &r"x\"), ("b", &" || id != ")],
+    )
+    .unwrap_err();
+    assert_eq!(err.value, r"x\");
+
+    let err = Filter::with_params("path = {:path}", &[("path", &Some(r"C:\tmp\"))]).unwrap_err();
+    assert_eq!(err.value, r"C:\tmp\");
+}
+
+#[tokio::test]
+async fn records_list_accepts_filter_builder() {
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "(status = 'active' && views >= 10)");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client
+        .records("posts")
+        .list()
+        .filter(
+            Filter::eq("status", "active")
+                .unwrap()
+                .and(Filter::gte("views", 10).unwrap()),
+        )
+        .call::<Value>()
+        .await
+        .unwrap();
+
+    list_mock.assert();
+    assert!(posts.items.is_empty());
+}
@@ -0,0 +1,42 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde_json::json;
+
+#[tokio::test]
+async fn health_check_exposes_server_data() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/health");
+        then.status(200).json_body(json!({
+            "code": 200,
+            "message": "API is healthy.",
+            "data": { "canBackup": true }
+        }));
+    });
+
+    let health = Client::new(server.base_url().as_str())
+        .health_check()
+        .await
+        .unwrap();
+
+    assert_eq!(health.code, 200);
+    assert_eq!(health.can_backup(), Some(true));
+}
+
+#[tokio::test]
+async fn health_check_without_data_from_older_servers() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/health");
+        then.status(200)
+            .json_body(json!({ "code": 200, "message": "API is healthy." }));
+    });
+
+    let health = Client::new(server.base_url().as_str())
+        .health_check()
+        .await
+        .unwrap();
+
+    assert!(health.data.is_none());
+    assert_eq!(health.can_backup(), None);
+}
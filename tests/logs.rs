@@ -0,0 +1,276 @@
+use chrono::{TimeZone, Utc};
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::logs::{LogAuthContext, LogList};
+use serde_json::{json, Value};
+
+fn log_item(id: &str, meta: Value) -> Value {
+    json!({
+        "id": id,
+        "created": "2022-06-24T06:24:18.434Z",
+        "updated": "2022-06-24T06:24:18.434Z",
+        "url": "/api/collections/posts/records",
+        "method": "GET",
+        "status": 200,
+        "ip": "127.0.0.1",
+        "referer": "",
+        "userAgent": "curl/8.0",
+        "meta": meta
+    })
+}
+
+#[tokio::test]
+async fn log_auth_context_for_user_and_guest() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/logs/requests");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "page": 1,
+                "perPage": 100,
+                "totalItems": 2,
+                "items": [
+                    log_item("user_log", json!({ "auth": "users", "authId": "8171022dc95a4ed" })),
+                    log_item("guest_log", json!({})),
+                ]
+            }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let logs = client.logs().list().call().await.unwrap();
+    assert_eq!(logs.total_pages, -1);
+    assert!(!logs.has_next_page());
+
+    assert_eq!(
+        logs.items[0].auth(),
+        LogAuthContext::User {
+            collection: Some("users".to_string()),
+            id: Some("8171022dc95a4ed".to_string()),
+        }
+    );
+    assert_eq!(logs.items[1].auth(), LogAuthContext::Guest);
+}
+
+#[tokio::test]
+async fn log_auth_context_for_admin() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/logs/requests/admin_log");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(log_item(
+                "admin_log",
+                json!({ "auth": "admin", "authId": "sywbhecnh46rhm0" }),
+            ));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let log = client.logs().view("admin_log").call().await.unwrap();
+
+    assert_eq!(
+        log.auth(),
+        LogAuthContext::Admin {
+            id: Some("sywbhecnh46rhm0".to_string()),
+        }
+    );
+}
+
+#[tokio::test]
+async fn log_auth_context_for_generic_auth_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/logs/requests/record_log");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(log_item(
+                "record_log",
+                json!({ "auth": "authRecord", "authId": "8171022dc95a4ed" }),
+            ));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let log = client.logs().view("record_log").call().await.unwrap();
+
+    assert_eq!(
+        log.auth(),
+        LogAuthContext::User {
+            collection: None,
+            id: Some("8171022dc95a4ed".to_string()),
+        }
+    );
+}
+
+#[tokio::test]
+async fn get_all_pages_through_every_log() {
+    let server = MockServer::start();
+    let first = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/logs/requests")
+            .query_param("filter", "status>=400")
+            .query_param("sort", "-created,id")
+            .query_param("perPage", "2")
+            .query_param("page", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 2,
+            "totalItems": 3,
+            "items": [log_item("l1", json!({})), log_item("l2", json!({}))]
+        }));
+    });
+    // A log was pruned after the first page was read.
+    let second = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/logs/requests")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 2,
+            "totalItems": 2,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(2);
+    let logs = client
+        .logs()
+        .get_all(Some("status>=400"), Some("-created"))
+        .await
+        .unwrap();
+
+    first.assert();
+    second.assert();
+    let ids: Vec<_> = logs.iter().map(|log| log.id.as_str()).collect();
+    assert_eq!(ids, ["l1", "l2"]);
+}
+
+#[test]
+fn log_list_without_total_items() {
+    let list: LogList = serde_json::from_value(json!({
+        "page": 1,
+        "perPage": 30,
+        "items": [log_item("l1", json!({}))]
+    }))
+    .unwrap();
+
+    assert_eq!(list.total_items, -1);
+    assert_eq!(list.total_pages, -1);
+    assert!(!list.has_next_page());
+}
+
+#[tokio::test]
+async fn log_list_sends_extra_query_params() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/logs/requests")
+            .query_param("sort", "-created")
+            .query_param("fields", "id,url");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    client
+        .logs()
+        .list()
+        .sort("-created")
+        .query_param("fields", "id,url")
+        .call()
+        .await
+        .unwrap();
+    list.assert();
+}
+
+#[tokio::test]
+async fn log_meta_with_nested_values() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/logs/requests/failed_log");
+        then.status(200).json_body(log_item(
+            "failed_log",
+            json!({
+                "auth": "users",
+                "authId": "8171022dc95a4ed",
+                "execTime": 1.25,
+                "status": 400,
+                "errorMessage": "Failed to create record.",
+                "errorDetails": {
+                    "title": {
+                        "code": "validation_required",
+                        "message": "Missing required value."
+                    }
+                }
+            }),
+        ));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let log = client.logs().view("failed_log").call().await.unwrap();
+
+    assert_eq!(log.meta["execTime"], 1.25);
+    assert_eq!(log.meta["status"], 400);
+    assert_eq!(
+        log.meta["errorDetails"]["title"]["code"],
+        "validation_required"
+    );
+    assert!(matches!(log.auth(), LogAuthContext::User { .. }));
+}
+
+#[tokio::test]
+async fn log_statistics_parse_dates() {
+    let server = MockServer::start();
+    let stats = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/logs/requests/stats")
+            .query_param("filter", "status >= 400");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!([
+                { "total": 4, "date": "2022-06-01 19:00:00.000Z" },
+                { "total": 1, "date": "2022-06-02 12:00:00.000Z" },
+                { "total": 8, "date": "2022-06-02 13:00:00.000" }
+            ]));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let points = client
+        .logs()
+        .statistics()
+        .filter("status >= 400")
+        .call()
+        .await
+        .unwrap();
+
+    stats.assert();
+    assert_eq!(points.len(), 3);
+    assert_eq!(points[0].total, 4);
+    assert_eq!(
+        points[0].date,
+        Utc.with_ymd_and_hms(2022, 6, 1, 19, 0, 0).unwrap()
+    );
+    assert_eq!(
+        points[2].date,
+        Utc.with_ymd_and_hms(2022, 6, 2, 13, 0, 0).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn log_statistics_reject_invalid_dates() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/logs/requests/stats");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!([{ "total": 4, "date": "yesterday" }]));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client.logs().statistics().call().await.unwrap_err();
+
+    assert!(format!("{:#}", err).contains("yesterday"), "{:#}", err);
+}
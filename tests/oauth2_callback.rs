@@ -0,0 +1,160 @@
+#![cfg(feature = "oauth2-callback")]
+
+use httpmock::prelude::*;
+use pocketbase_client::client::{AuthError, AuthProviderInfo, Client};
+use pocketbase_client::oauth2::OAuth2Callback;
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+fn github() -> AuthProviderInfo {
+    AuthProviderInfo {
+        name: "github".to_string(),
+        display_name: "GitHub".to_string(),
+        state: "state-abc".to_string(),
+        auth_url: "https://github.com/login/oauth/authorize?state=state-abc&redirect_uri="
+            .to_string(),
+        code_verifier: "verifier-123".to_string(),
+        code_challenge: "challenge-xyz".to_string(),
+        code_challenge_method: "S256".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn callback_completes_oauth2_login() {
+    let callback = OAuth2Callback::bind("127.0.0.1:0").await.unwrap();
+    let redirect_url = callback.redirect_url().to_string();
+    assert!(callback
+        .auth_url(&github())
+        .contains("redirect_uri=http%3A%2F%2F127.0.0.1%3A"));
+
+    let server = MockServer::start();
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-oauth2")
+            .json_body(json!({
+                "provider": "github",
+                "code": "provider-code",
+                "codeVerifier": "verifier-123",
+                "redirectUrl": redirect_url
+            }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "token": "oauth2-token",
+                "record": { "id": "8171022dc95a4ed" }
+            }));
+    });
+
+    // The browser following the provider's redirect.
+    let browser = tokio::spawn(async move {
+        reqwest::get(format!(
+            "{}?code=provider-code&state=state-abc",
+            redirect_url
+        ))
+        .await
+        .unwrap()
+        .status()
+    });
+
+    let client = Client::new(server.base_url().as_str())
+        .auth_with_oauth2_callback("users", &github(), callback, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    login.assert();
+    assert_eq!(browser.await.unwrap(), 200);
+    assert_eq!(client.auth_token().as_deref(), Some("oauth2-token"));
+}
+
+#[tokio::test]
+async fn callback_rejects_mismatched_state() {
+    let callback = OAuth2Callback::bind("127.0.0.1:0").await.unwrap();
+    let redirect_url = callback.redirect_url().to_string();
+    let browser = tokio::spawn(async move {
+        reqwest::get(format!("{}?code=provider-code&state=forged", redirect_url))
+            .await
+            .unwrap()
+            .status()
+    });
+
+    let result = Client::new("http://127.0.0.1:1")
+        .auth_with_oauth2_callback("users", &github(), callback, Duration::from_secs(5))
+        .await;
+
+    assert!(matches!(result, Err(AuthError::Other(msg)) if msg.contains("state")));
+    assert_eq!(browser.await.unwrap(), 400);
+}
+
+#[tokio::test]
+async fn callback_reports_provider_errors_as_plain_text() {
+    let callback = OAuth2Callback::bind("127.0.0.1:0").await.unwrap();
+    let redirect_url = callback.redirect_url().to_string();
+    let browser = tokio::spawn(async move {
+        let response = reqwest::get(format!(
+            "{}?error=%3Cscript%3Ealert(1)%3C%2Fscript%3E",
+            redirect_url
+        ))
+        .await
+        .unwrap();
+        let content_type = response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        (content_type, response.text().await.unwrap())
+    });
+
+    let result = Client::new("http://127.0.0.1:1")
+        .auth_with_oauth2_callback("users", &github(), callback, Duration::from_secs(5))
+        .await;
+
+    assert!(matches!(result, Err(AuthError::Other(msg)) if msg.contains("<script>")));
+    let (content_type, body) = browser.await.unwrap();
+    assert!(content_type.starts_with("text/plain"));
+    assert!(body.contains("<script>alert(1)</script>"));
+}
+
+#[tokio::test]
+async fn callback_rejects_oversized_requests_and_keeps_waiting() {
+    let callback = OAuth2Callback::bind("127.0.0.1:0").await.unwrap();
+    let redirect_url = callback.redirect_url().to_string();
+    let addr = redirect_url
+        .trim_start_matches("http://")
+        .trim_end_matches("/callback")
+        .to_string();
+    let browser = tokio::spawn(async move {
+        // One byte over the 8 KiB cap, without ever ending the head.
+        let mut head = "GET /callback HTTP/1.1\r\nX-Filler: ".to_string();
+        head.push_str(&"a".repeat(8 * 1024 + 1 - head.len()));
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        stream.write_all(head.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        let forged = reqwest::get(format!("{}?code=provider-code&state=forged", redirect_url))
+            .await
+            .unwrap();
+        (response, forged.status().as_u16())
+    });
+
+    let result = Client::new("http://127.0.0.1:1")
+        .auth_with_oauth2_callback("users", &github(), callback, Duration::from_secs(5))
+        .await;
+
+    assert!(matches!(result, Err(AuthError::Other(msg)) if msg.contains("state")));
+    let (response, forged_status) = browser.await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 "), "{}", response);
+    assert_eq!(forged_status, 400);
+}
+
+#[tokio::test]
+async fn callback_times_out_when_user_cancels() {
+    let callback = OAuth2Callback::bind("127.0.0.1:0").await.unwrap();
+
+    let result = Client::new("http://127.0.0.1:1")
+        .auth_with_oauth2_callback("users", &github(), callback, Duration::from_millis(50))
+        .await;
+
+    assert!(matches!(result, Err(AuthError::Other(msg)) if msg.contains("timed out")));
+}
@@ -0,0 +1,77 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::observer::RequestEvent;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn observer_sees_every_request() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "totalPages": 0,
+            "items": []
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records/missing");
+        then.status(404).json_body(json!({
+            "code": 404,
+            "message": "The requested resource wasn't found.",
+            "data": {}
+        }));
+    });
+
+    let events: Arc<Mutex<Vec<RequestEvent>>> = Arc::default();
+    let recorded = Arc::clone(&events);
+    let client = Client::new(server.base_url().as_str()).with_request_observer(
+        move |event: &RequestEvent| recorded.lock().unwrap().push(event.clone()),
+    );
+
+    client
+        .records("posts")
+        .list()
+        .call::<Value>()
+        .await
+        .unwrap();
+    assert!(client
+        .records("posts")
+        .view("missing")
+        .call::<Value>()
+        .await
+        .is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].method, "GET");
+    assert!(events[0]
+        .url
+        .starts_with(&server.url("/api/collections/posts/records?")));
+    assert_eq!(events[0].status.map(|s| s.as_u16()), Some(200));
+    assert_eq!(
+        events[1].url,
+        server.url("/api/collections/posts/records/missing")
+    );
+    assert_eq!(events[1].status.map(|s| s.as_u16()), Some(404));
+}
+
+#[tokio::test]
+async fn observer_reports_missing_status_on_connection_error() {
+    let events: Arc<Mutex<Vec<RequestEvent>>> = Arc::default();
+    let recorded = Arc::clone(&events);
+    let client =
+        Client::new("http://127.0.0.1:1").with_request_observer(move |event: &RequestEvent| {
+            recorded.lock().unwrap().push(event.clone())
+        });
+
+    assert!(client.health_check().await.is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].status, None);
+}
@@ -0,0 +1,77 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::{AuthError, Client};
+use serde_json::json;
+
+#[tokio::test]
+async fn request_password_reset_success() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/request-password-reset")
+            .json_body(json!({ "email": "test@example.com" }));
+        then.status(204);
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    client
+        .request_password_reset("users", "test@example.com")
+        .await
+        .unwrap();
+    mock.assert();
+}
+
+#[tokio::test]
+async fn confirm_password_reset_success() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/confirm-password-reset")
+            .json_body(json!({
+                "token": "reset-token",
+                "password": "new-password",
+                "passwordConfirm": "new-password"
+            }));
+        then.status(204);
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    client
+        .confirm_password_reset("users", "reset-token", "new-password", "new-password")
+        .await
+        .unwrap();
+    mock.assert();
+}
+
+#[tokio::test]
+async fn confirm_password_reset_validation_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/confirm-password-reset");
+        then.status(400)
+            .header("Content-Type", "application/json")
+            .json_body(json!({
+                "code": 400,
+                "message": "Failed to authenticate.",
+                "data": {
+                    "token": {
+                        "code": "validation_invalid_token",
+                        "message": "Invalid or expired token."
+                    }
+                }
+            }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .confirm_password_reset("users", "expired", "new-password", "new-password")
+        .await
+        .unwrap_err();
+    match err {
+        AuthError::Validation(body) => {
+            assert_eq!(body.status, 400);
+            assert_eq!(body.data["token"].code, "validation_invalid_token");
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
@@ -0,0 +1,29 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Proxy;
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn requests_go_through_proxy_with_client_settings() {
+    let proxy = MockServer::start();
+    let health = proxy.mock(|when, then| {
+        when.method(GET)
+            .path("/api/health")
+            .header("x-tenant", "acme");
+        then.status(200)
+            .json_body(json!({ "code": 200, "message": "API is healthy." }));
+    });
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant", HeaderValue::from_static("acme"));
+    let client = Client::new("http://pocketbase.invalid")
+        .with_timeout(Duration::from_secs(5))
+        .with_default_headers(headers)
+        .with_proxy(Proxy::http(proxy.base_url()).unwrap());
+    assert_eq!(client.timeout, Some(Duration::from_secs(5)));
+
+    client.health_check().await.unwrap();
+    health.assert();
+}
@@ -0,0 +1,64 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::error::{
+    RateLimitedError, RecordListError, RecordViewError, RecordWriteError,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+fn rate_limited_server() -> MockServer {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path_contains("/api/collections/posts/records");
+        then.status(429)
+            .header("Retry-After", "7")
+            .json_body(json!({ "code": 429, "message": "Too Many Requests.", "data": {} }));
+    });
+    server
+}
+
+#[tokio::test]
+async fn view_and_write_report_rate_limit() {
+    let server = rate_limited_server();
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let posts = client.records("posts");
+
+    let err = posts.view("a1").call::<Value>().await.unwrap_err();
+    assert!(matches!(
+        err,
+        RecordViewError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(7)
+    ));
+
+    let err = posts
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RecordWriteError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(7)
+    ));
+
+    let err = posts
+        .update("a1", json!({ "title": "edited" }))
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RecordWriteError::RateLimited { .. }));
+}
+
+#[tokio::test]
+async fn list_and_delete_report_rate_limit() {
+    let server = rate_limited_server();
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let posts = client.records("posts");
+
+    let err = posts.list().call::<Value>().await.unwrap_err();
+    assert!(matches!(
+        err,
+        RecordListError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(7)
+    ));
+
+    let err = posts.destroy("a1").call().await.unwrap_err();
+    assert!(err.downcast_ref::<RateLimitedError>().is_some());
+}
@@ -0,0 +1,481 @@
+use futures::StreamExt;
+use pocketbase_client::client::Client;
+use pocketbase_client::rts::{ConnectionState, RealtimeAction};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
+use tokio::time::timeout;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Post {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub body: String,
+}
+
+/// A minimal PocketBase realtime endpoint: every `GET /api/realtime` opens an
+/// event stream starting with `PB_CONNECT`, every `POST /api/realtime` is
+/// recorded and acknowledged with a 204, or answered with a 400 while
+/// `rejecting` is set.
+struct RealtimeServer {
+    url: String,
+    streams: UnboundedReceiver<TcpStream>,
+    subscriptions: UnboundedReceiver<Value>,
+    rejecting: Arc<AtomicBool>,
+}
+
+impl RealtimeServer {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (stream_tx, streams) = unbounded_channel();
+        let (subscription_tx, subscriptions) = unbounded_channel();
+        let rejecting = Arc::new(AtomicBool::new(false));
+        let reject = Arc::clone(&rejecting);
+        tokio::spawn(async move {
+            let mut connections = 0;
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                connections += 1;
+                tokio::spawn(handle(
+                    socket,
+                    format!("client-{}", connections),
+                    stream_tx.clone(),
+                    subscription_tx.clone(),
+                    Arc::clone(&reject),
+                ));
+            }
+        });
+        RealtimeServer {
+            url,
+            streams,
+            subscriptions,
+            rejecting,
+        }
+    }
+
+    async fn next_stream(&mut self) -> TcpStream {
+        timeout(Duration::from_secs(5), self.streams.recv())
+            .await
+            .expect("no realtime connection opened")
+            .unwrap()
+    }
+
+    async fn next_subscription(&mut self) -> Value {
+        timeout(Duration::from_secs(5), self.subscriptions.recv())
+            .await
+            .expect("no subscription update received")
+            .unwrap()
+    }
+}
+
+async fn handle(
+    mut socket: TcpStream,
+    client_id: String,
+    streams: UnboundedSender<TcpStream>,
+    subscriptions: UnboundedSender<Value>,
+    rejecting: Arc<AtomicBool>,
+) {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        let read = socket.read(&mut buf).await.unwrap();
+        if read == 0 {
+            return;
+        }
+        request.extend_from_slice(&buf[..read]);
+        if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&request[..header_end]).to_string();
+
+    if head.starts_with("GET /api/realtime") {
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        send_event(&mut socket, "PB_CONNECT", json!({ "clientId": client_id })).await;
+        streams.send(socket).unwrap();
+    } else if head.starts_with("POST /api/realtime") && rejecting.load(Ordering::SeqCst) {
+        socket
+            .write_all(
+                b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .unwrap();
+    } else if head.starts_with("POST /api/realtime") {
+        let length: usize = head
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse().unwrap())
+            })
+            .unwrap_or(0);
+        let mut body = request[header_end..].to_vec();
+        while body.len() < length {
+            let read = socket.read(&mut buf).await.unwrap();
+            body.extend_from_slice(&buf[..read]);
+        }
+        subscriptions
+            .send(serde_json::from_slice(&body).unwrap())
+            .unwrap();
+        socket
+            .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+    }
+}
+
+async fn send_event(socket: &mut TcpStream, topic: &str, data: Value) {
+    let event = format!("event:{}\ndata:{}\n\n", topic, data);
+    socket.write_all(event.as_bytes()).await.unwrap();
+}
+
+#[tokio::test]
+async fn realtime_subscription_receives_typed_events() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let mut events = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let mut stream = server.next_stream().await;
+    assert_eq!(
+        server.next_subscription().await,
+        json!({ "clientId": "client-1", "subscriptions": ["posts"] })
+    );
+
+    send_event(
+        &mut stream,
+        "posts",
+        json!({ "action": "create", "record": { "id": "a1", "title": "hello" } }),
+    )
+    .await;
+
+    let event = timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.topic, "posts");
+    assert_eq!(event.action, RealtimeAction::Create);
+    assert_eq!(event.record.title, "hello");
+}
+
+#[tokio::test]
+async fn realtime_events_split_inside_a_character_are_decoded() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let mut events = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let mut stream = server.next_stream().await;
+    server.next_subscription().await;
+
+    let event = format!(
+        "event:posts\r\ndata:{}\r\n\r\n",
+        json!({ "action": "update", "record": { "id": "a1", "title": "café" } })
+    );
+    let split = event.find('é').unwrap() + 1;
+    stream.write_all(&event.as_bytes()[..split]).await.unwrap();
+    stream.flush().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    stream.write_all(&event.as_bytes()[split..]).await.unwrap();
+
+    let event = timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.record.title, "café");
+}
+
+#[tokio::test]
+async fn realtime_resubscribes_after_reconnect() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let _events = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let stream = server.next_stream().await;
+    server.next_subscription().await;
+    drop(stream);
+
+    let _stream = server.next_stream().await;
+    let resubscription = server.next_subscription().await;
+    assert_ne!(resubscription["clientId"], "client-1");
+    assert_eq!(resubscription["subscriptions"], json!(["posts"]));
+}
+
+#[tokio::test]
+async fn realtime_duplicate_topic_is_registered_once() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let mut first = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let mut stream = server.next_stream().await;
+    assert_eq!(
+        server.next_subscription().await,
+        json!({ "clientId": "client-1", "subscriptions": ["posts"] })
+    );
+
+    let mut second = realtime
+        .subscribe::<Post>(&["posts", "posts"])
+        .await
+        .unwrap();
+    assert!(
+        timeout(Duration::from_millis(300), server.subscriptions.recv())
+            .await
+            .is_err(),
+        "duplicate subscribe should not update the server"
+    );
+
+    send_event(
+        &mut stream,
+        "posts",
+        json!({ "action": "update", "record": { "id": "a1", "title": "edited" } }),
+    )
+    .await;
+    for events in [&mut first, &mut second] {
+        let event = timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.action, RealtimeAction::Update);
+    }
+    assert!(timeout(Duration::from_millis(100), second.next())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn realtime_channels_route_events_per_collection() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let mut posts = realtime.channel::<Post>("posts").await.unwrap();
+
+    let mut stream = server.next_stream().await;
+    server.next_subscription().await;
+    let mut comments = realtime.channel::<Comment>("comments").await.unwrap();
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["comments", "posts"])
+    );
+
+    send_event(
+        &mut stream,
+        "comments",
+        json!({ "action": "create", "record": { "id": "c1", "body": "first!" } }),
+    )
+    .await;
+    send_event(
+        &mut stream,
+        "posts",
+        json!({ "action": "delete", "record": { "id": "a1", "title": "hello" } }),
+    )
+    .await;
+
+    let comment = timeout(Duration::from_secs(5), comments.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(comment.topic, "comments");
+    assert_eq!(comment.record.body, "first!");
+
+    let post = timeout(Duration::from_secs(5), posts.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.topic, "posts");
+    assert_eq!(post.action, RealtimeAction::Delete);
+    assert_eq!(post.record.id, "a1");
+
+    assert!(timeout(Duration::from_millis(100), comments.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn realtime_subscribes_coalesce_into_one_update() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let _posts = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let _stream = server.next_stream().await;
+    server.next_subscription().await;
+
+    let _comments = realtime.subscribe::<Comment>(&["comments"]).await.unwrap();
+    let _tags = realtime.subscribe::<Value>(&["tags"]).await.unwrap();
+    let _single = realtime.subscribe::<Post>(&["posts/a1"]).await.unwrap();
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["comments", "posts", "posts/a1", "tags"])
+    );
+    assert!(
+        timeout(Duration::from_millis(300), server.subscriptions.recv())
+            .await
+            .is_err(),
+        "subscribes should be sent as a single update"
+    );
+}
+
+#[tokio::test]
+async fn realtime_flush_sends_pending_subscriptions() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let _posts = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let _stream = server.next_stream().await;
+    server.next_subscription().await;
+
+    let _comments = realtime.subscribe::<Comment>(&["comments"]).await.unwrap();
+    realtime.flush().await.unwrap();
+    let update = timeout(Duration::from_millis(20), server.subscriptions.recv())
+        .await
+        .expect("flush should send the update immediately")
+        .unwrap();
+    assert_eq!(update["subscriptions"], json!(["comments", "posts"]));
+}
+
+#[tokio::test]
+async fn realtime_unsubscribe_updates_the_server() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let _posts = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+    let mut single = realtime.subscribe::<Post>(&["posts/a1"]).await.unwrap();
+
+    let stream = server.next_stream().await;
+    server.next_subscription().await;
+    assert_eq!(realtime.client_id().as_deref(), Some("client-1"));
+
+    realtime.unsubscribe(&["posts/a1"]).await.unwrap();
+    realtime.flush().await.unwrap();
+    assert_eq!(
+        server.next_subscription().await,
+        json!({ "clientId": "client-1", "subscriptions": ["posts"] })
+    );
+    assert_eq!(realtime.topics(), vec!["posts".to_string()]);
+    assert!(timeout(Duration::from_secs(5), single.next())
+        .await
+        .unwrap()
+        .is_none());
+
+    drop(stream);
+    let _stream = server.next_stream().await;
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["posts"])
+    );
+}
+
+async fn next_state(state: &mut watch::Receiver<ConnectionState>) -> ConnectionState {
+    timeout(Duration::from_secs(5), state.changed())
+        .await
+        .expect("connection state did not change")
+        .unwrap();
+    *state.borrow_and_update()
+}
+
+#[tokio::test]
+async fn realtime_connection_state_tracks_drops() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let mut state = realtime.connection_state();
+    assert_eq!(*state.borrow_and_update(), ConnectionState::Connecting);
+
+    let stream = server.next_stream().await;
+    assert_eq!(next_state(&mut state).await, ConnectionState::Connected);
+
+    drop(stream);
+    assert_eq!(next_state(&mut state).await, ConnectionState::Reconnecting);
+
+    let _stream = server.next_stream().await;
+    assert_eq!(next_state(&mut state).await, ConnectionState::Connected);
+
+    drop(realtime);
+    assert_eq!(next_state(&mut state).await, ConnectionState::Disconnected);
+}
+
+#[tokio::test]
+async fn realtime_reports_rejected_subscriptions_on_their_stream() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let state = realtime.connection_state();
+    let mut posts = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+
+    let _stream = server.next_stream().await;
+    server.next_subscription().await;
+
+    server.rejecting.store(true, Ordering::SeqCst);
+    let mut comments = realtime.subscribe::<Comment>(&["comments"]).await.unwrap();
+    let err = timeout(Duration::from_secs(5), comments.next())
+        .await
+        .expect("the rejected subscription should be reported")
+        .unwrap()
+        .unwrap_err();
+    assert!(err.to_string().contains("HTTP 400"), "{}", err);
+    assert!(timeout(Duration::from_millis(100), posts.next())
+        .await
+        .is_err());
+    assert_eq!(*state.borrow(), ConnectionState::Connected);
+
+    server.rejecting.store(false, Ordering::SeqCst);
+    realtime.flush().await.unwrap();
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["comments", "posts"])
+    );
+}
+
+#[tokio::test]
+async fn realtime_unsubscribes_topics_whose_streams_were_dropped() {
+    let mut server = RealtimeServer::start().await;
+    let client = Client::new(&server.url);
+    let realtime = client.realtime().connect();
+    let posts = realtime.subscribe::<Post>(&["posts"]).await.unwrap();
+    let _comments = realtime.subscribe::<Comment>(&["comments"]).await.unwrap();
+
+    let mut stream = server.next_stream().await;
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["comments", "posts"])
+    );
+
+    drop(posts);
+    send_event(
+        &mut stream,
+        "posts",
+        json!({ "action": "create", "record": { "id": "a1", "title": "hello" } }),
+    )
+    .await;
+
+    assert_eq!(
+        server.next_subscription().await["subscriptions"],
+        json!(["comments"])
+    );
+    assert_eq!(realtime.topics(), vec!["comments".to_string()]);
+}
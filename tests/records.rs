@@ -1,5 +1,15 @@
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
 use httpmock::prelude::*;
 use pocketbase_client::client::Client;
+use pocketbase_client::collections::Collection;
+use pocketbase_client::error::{
+    BodyTooLargeError, FieldValidationError, PageLimitError, RecordListError, RecordViewError,
+    RecordWriteError,
+};
+use pocketbase_client::filter::Filter;
+use pocketbase_client::models::{AuthRecord, BaseModel, Expanded, WriteOnly};
+use pocketbase_client::records::{Outcome, QueryParams};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -9,6 +19,14 @@ pub struct Record {
     pub title: String,
 }
 
+#[derive(Clone, Debug, Serialize, Default, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub email: String,
+    #[serde(default, skip_serializing_if = "WriteOnly::is_unset")]
+    pub password: WriteOnly<String>,
+}
+
 #[tokio::test]
 async fn list_records_success() {
     let mockserver = mock_records_server();
@@ -26,8 +44,2282 @@ async fn list_records_success() {
     assert!(records.is_ok());
 }
 
-fn mock_records_server() -> MockServer {
+#[tokio::test]
+async fn create_record_with_file_from_path() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let path = std::env::temp_dir().join("pocketbase-client-upload.txt");
+    std::fs::write(&path, "uploaded file contents").unwrap();
+
+    let response = client
+        .records("posts")
+        .create(json!({ "title": "test3" }))
+        .file_from_path("document", &path)
+        .call()
+        .await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(response.unwrap().id, "f1e2d3c4b5a6978");
+}
+
+#[tokio::test]
+async fn create_response_parses_timestamps() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "@collectionId": "a98f514eb05f454",
+            "@collectionName": "posts",
+            "id": "n3w",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-26 09:12:01.120Z",
+            "title": "fresh"
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let created = client
+        .records("posts")
+        .create(json!({ "title": "fresh" }))
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(created.collection_name.as_deref(), Some("posts"));
+    assert_eq!(
+        created.created,
+        "2022-06-25T11:03:35.163Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    assert_eq!(
+        created.updated,
+        "2022-06-26T09:12:01.120Z".parse::<DateTime<Utc>>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn write_calls_return_status() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(201).json_body(json!({
+            "id": "n3w",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-25 11:03:35.163Z",
+            "title": "fresh"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/n3w");
+        then.status(204);
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let (status, created) = client
+        .records("posts")
+        .create(json!({ "title": "fresh" }))
+        .call_with_status()
+        .await
+        .unwrap();
+    assert_eq!(status.as_u16(), 201);
+    assert_eq!(created.id, "n3w");
+
+    let status = client
+        .records("posts")
+        .destroy("n3w")
+        .call_with_status()
+        .await
+        .unwrap();
+    assert_eq!(status.as_u16(), 204);
+}
+
+#[tokio::test]
+async fn create_validation_failure_exposes_field_errors() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(400).json_body(json!({
+            "code": 400,
+            "message": "Failed to create record.",
+            "data": {
+                "title": {
+                    "code": "validation_required",
+                    "message": "Missing required value."
+                }
+            }
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .create(json!({ "title": "" }))
+        .call()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordWriteError::Validation(response) => {
+            assert_eq!(response.status, 400);
+            assert_eq!(response.data["title"].code, "validation_required");
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn create_reports_error_body_sent_with_success_status() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "code": 400,
+            "message": "Failed to create record: hook rejected the title.",
+            "data": {}
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap_err();
+    match err {
+        RecordWriteError::Validation(err) => {
+            assert_eq!(err.status, 400);
+            assert_eq!(
+                err.message,
+                "Failed to create record: hook rejected the title."
+            );
+        }
+        other => panic!("expected the server's error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn create_reports_empty_and_non_json_bodies() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/empty/records");
+        then.status(204);
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/proxied/records");
+        then.status(502)
+            .header("content-type", "text/html")
+            .body("<html><body>502 Bad Gateway</body></html>");
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/html/records");
+        then.status(200)
+            .header("content-type", "text/html")
+            .body("<html><body>Maintenance</body></html>");
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = json!({ "title": "hello" });
+
+    let err = client
+        .records("empty")
+        .create(record.clone())
+        .call()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, RecordWriteError::EmptyBody { status: 204, .. }),
+        "{:?}",
+        err
+    );
+
+    let err = client
+        .records("proxied")
+        .create(record.clone())
+        .call()
+        .await
+        .unwrap_err();
+    match err {
+        RecordWriteError::Http {
+            status,
+            body_snippet,
+            ..
+        } => {
+            assert_eq!(status, 502);
+            assert!(body_snippet.contains("502 Bad Gateway"));
+        }
+        other => panic!("expected an HTTP error, got {:?}", other),
+    }
+
+    let err = client
+        .records("html")
+        .create(record)
+        .call()
+        .await
+        .unwrap_err();
+    match err {
+        RecordWriteError::Decode { body_snippet, .. } => {
+            assert!(body_snippet.starts_with("<html><body>Maintenance"))
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn create_failure_truncates_non_ascii_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordWriteError::Http { body_snippet, .. } => {
+            assert_eq!(body_snippet, "€".repeat(666))
+        }
+        other => panic!("expected an HTTP error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn create_decode_failure_truncates_non_ascii_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(200).body("€".repeat(1000));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordWriteError::Decode { body_snippet, .. } => {
+            assert_eq!(body_snippet, "€".repeat(666))
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn create_rejects_select_value_outside_schema() {
+    let server = MockServer::start();
+    let create_mock = server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(400);
+    });
+    let posts: Collection = serde_json::from_value(json!({
+        "id": "a98f514eb05f454",
+        "created": "2022-06-22 07:13:00.643Z",
+        "updated": "2022-06-22 07:13:00.643Z",
+        "name": "posts",
+        "type": "base",
+        "schema": [{
+            "system": false,
+            "id": "st4tus00",
+            "name": "status",
+            "type": "select",
+            "required": true,
+            "unique": false,
+            "options": { "maxSelect": 1, "values": ["draft", "published"] }
+        }]
+    }))
+    .unwrap();
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .create(json!({ "title": "hello", "status": "archived" }))
+        .validate_with(&posts)
+        .call()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordWriteError::InvalidField(FieldValidationError::InvalidSelectValue {
+            field,
+            value,
+            ..
+        }) => {
+            assert_eq!(field, "status");
+            assert_eq!(value, "archived");
+        }
+        other => panic!("expected a select validation error, got {:?}", other),
+    }
+    create_mock.assert_hits(0);
+}
+
+#[tokio::test]
+async fn delete_many_reports_each_id() {
+    let server = MockServer::start();
+    let deletes: Vec<_> = ["a1", "b2"]
+        .iter()
+        .map(|id| {
+            server.mock(|when, then| {
+                when.method(DELETE)
+                    .path(format!("/api/collections/posts/records/{}", id));
+                then.status(204);
+            })
+        })
+        .collect();
+    server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/gone");
+        then.status(404).json_body(json!({
+            "code": 404,
+            "message": "The requested resource wasn't found.",
+            "data": {}
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let mut report = client
+        .records("posts")
+        .delete_many(&["a1", "gone", "b2"], 2)
+        .await
+        .unwrap();
+
+    report.deleted.sort();
+    assert_eq!(report.deleted, ["a1", "b2"]);
+    assert_eq!(report.not_found, ["gone"]);
+    assert!(report.failed.is_empty());
+    for delete in &deletes {
+        delete.assert();
+    }
+}
+
+#[tokio::test]
+async fn delete_all_deletes_every_matching_record() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "draft = true")
+            .query_param("fields", "id");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 500,
+            "totalItems": 3,
+            "totalPages": 1,
+            "items": [{ "id": "a1" }, { "id": "gone" }, { "id": "b2" }]
+        }));
+    });
+    let deletes: Vec<_> = ["a1", "b2"]
+        .iter()
+        .map(|id| {
+            server.mock(|when, then| {
+                when.method(DELETE)
+                    .path(format!("/api/collections/posts/records/{}", id));
+                then.status(204);
+            })
+        })
+        .collect();
+    server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/gone");
+        then.status(404).json_body(json!({
+            "code": 404,
+            "message": "The requested resource wasn't found.",
+            "data": {}
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let deleted = client
+        .records("posts")
+        .delete_all("draft = true")
+        .concurrency(2)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(deleted, 2);
+    list.assert();
+    for delete in &deletes {
+        delete.assert();
+    }
+}
+
+#[tokio::test]
+async fn delete_all_reports_failed_deletes() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 500,
+            "totalItems": 2,
+            "totalPages": 1,
+            "items": [{ "id": "a1" }, { "id": "locked" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/a1");
+        then.status(204);
+    });
+    server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/api/collections/posts/records/locked");
+        then.status(400).json_body(json!({
+            "code": 400,
+            "message": "Failed to delete record. Make sure that the record is not part of a required relation reference.",
+            "data": {}
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let err = client
+        .records("posts")
+        .delete_all("draft = true")
+        .call()
+        .await
+        .unwrap_err();
+
+    let message = format!("{:#}", err);
+    assert!(message.contains("locked"), "{}", message);
+    assert!(message.contains("1 of 2"), "{}", message);
+}
+
+#[tokio::test]
+async fn create_record_with_in_memory_files_sets_content_type() {
+    let server = MockServer::start();
+    let create_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .body_contains("filename=\"cover.png\"\r\nContent-Type: image/png")
+            .body_contains("filename=\"notes\"\r\nContent-Type: text/markdown");
+        then.status(200).json_body(json!({
+            "id": "f1e2d3c4b5a6978",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-25 11:03:35.163Z"
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let response = client
+        .records("posts")
+        .create(json!({ "title": "with cover" }))
+        .file_from_bytes("cover", "cover.png", b"\x89PNG\r\n".to_vec())
+        .file_with_mime("notes", "notes", b"# notes".to_vec(), "text/markdown")
+        .call()
+        .await;
+
+    assert_eq!(response.unwrap().id, "f1e2d3c4b5a6978");
+    create_mock.assert();
+}
+
+#[tokio::test]
+async fn update_returns_record_stored_by_server() {
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct Post {
+        id: String,
+        title: String,
+        updated: String,
+    }
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1");
+        then.status(200).json_body(json!({
+            "id": "a1",
+            "title": "edited",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-26 09:12:01.120Z"
+        }));
+    });
+
+    let submitted = Post {
+        id: "a1".to_string(),
+        title: "edited".to_string(),
+        updated: "2022-06-25 11:03:35.163Z".to_string(),
+    };
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let stored = client
+        .records("posts")
+        .update("a1", submitted.clone())
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(stored.title, "edited");
+    assert_eq!(stored.updated, "2022-06-26 09:12:01.120Z");
+    assert_ne!(stored.updated, submitted.updated);
+}
+
+#[tokio::test]
+async fn update_with_omit_none_leaves_out_unset_fields() {
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct PostPatch {
+        title: Option<String>,
+        body: Option<String>,
+    }
+
+    let server = MockServer::start();
+    let update = server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1")
+            .json_body(json!({ "title": "edited" }));
+        then.status(200)
+            .json_body(json!({ "title": "edited", "body": "kept" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let patch = PostPatch {
+        title: Some("edited".to_string()),
+        body: None,
+    };
+    let stored = client
+        .records("posts")
+        .update("a1", patch)
+        .omit_none()
+        .call()
+        .await
+        .unwrap();
+
+    update.assert();
+    assert_eq!(stored.body.as_deref(), Some("kept"));
+}
+
+#[tokio::test]
+async fn upsert_updates_existing_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1");
+        then.status(200)
+            .json_body(json!({ "id": "a1", "title": "edited" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: "a1".to_string(),
+        title: "edited".to_string(),
+    };
+    let outcome = client.records("posts").upsert("a1", record).await.unwrap();
+
+    assert!(matches!(outcome, Outcome::Updated(ref r) if r.title == "edited"));
+}
+
+#[tokio::test]
+async fn upsert_creates_missing_record_with_id() {
+    let server = MockServer::start();
+    let update = server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1");
+        then.status(404).json_body(json!({
+            "code": 404,
+            "message": "The requested resource wasn't found.",
+            "data": {}
+        }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .json_body(json!({ "id": "a1", "title": "new" }));
+        then.status(200)
+            .json_body(json!({ "id": "a1", "title": "new" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: String::new(),
+        title: "new".to_string(),
+    };
+    let outcome = client.records("posts").upsert("a1", record).await.unwrap();
+
+    update.assert();
+    create.assert();
+    assert!(matches!(outcome, Outcome::Created(ref r) if r.id == "a1"));
+}
+
+#[tokio::test]
+async fn upsert_reports_create_error_when_update_rule_hides_record() {
+    let server = MockServer::start();
+    let update = server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/posts/records/a1");
+        then.status(404).json_body(json!({
+            "code": 404,
+            "message": "The requested resource wasn't found.",
+            "data": {}
+        }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(400).json_body(json!({
+            "code": 400,
+            "message": "Failed to create record.",
+            "data": {
+                "id": {
+                    "code": "validation_invalid_id",
+                    "message": "The model id is invalid or already exists."
+                }
+            }
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: String::new(),
+        title: "hidden".to_string(),
+    };
+    let err = client
+        .records("posts")
+        .upsert("a1", record)
+        .await
+        .unwrap_err();
+
+    update.assert();
+    create.assert();
+    match err {
+        RecordWriteError::Validation(err) => assert!(err.data.contains_key("id")),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn first_returns_matching_record_or_none() {
+    let server = MockServer::start();
+    let found = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/users/records")
+            .query_param("filter", "email='test@example.com'")
+            .query_param("perPage", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "items": [{ "id": "u1", "email": "test@example.com" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/users/records")
+            .query_param("filter", "email='nobody@example.com'");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let users = client.records("users");
+    let user: Option<User> = users.first("email='test@example.com'").await.unwrap();
+    let missing: Option<User> = users.first("email='nobody@example.com'").await.unwrap();
+
+    found.assert();
+    assert_eq!(user.unwrap().id, "u1");
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn find_by_email_decodes_auth_record() {
+    #[derive(Debug, Default, Deserialize)]
+    struct Member {
+        #[serde(flatten)]
+        auth: AuthRecord,
+        name: String,
+    }
+
+    let server = MockServer::start();
+    let found = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/users/records")
+            .query_param("filter", "email = 'test@example.com'");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "items": [{
+                "id": "u1",
+                "collectionId": "_pb_users_auth_",
+                "collectionName": "users",
+                "created": "2022-06-24 06:24:18.434Z",
+                "updated": "2022-06-24 06:24:18.434Z",
+                "email": "test@example.com",
+                "emailVisibility": false,
+                "verified": true,
+                "name": "Test"
+            }]
+        }));
+    });
+    let verified = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/users/records")
+            .query_param("filter", "verified = true");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "totalPages": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let users = client.records("users");
+    let member: Member = users
+        .find_by_email("test@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.auth.base.id, "u1");
+    assert_eq!(member.auth.email, "test@example.com");
+    assert!(member.auth.verified);
+    assert!(!member.auth.email_visibility);
+    assert_eq!(member.name, "Test");
+
+    users
+        .list()
+        .filter(Filter::verified())
+        .call::<Member>()
+        .await
+        .unwrap();
+    found.assert();
+    verified.assert();
+}
+
+#[tokio::test]
+async fn create_or_get_returns_existing_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "title='hello'")
+            .query_param("perPage", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "totalPages": -1,
+            "items": [{ "id": "a1", "title": "hello" }]
+        }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST).path("/api/collections/posts/records");
+        then.status(200)
+            .json_body(json!({ "id": "b2", "title": "hello" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: String::new(),
+        title: "hello".to_string(),
+    };
+    let outcome = client
+        .records("posts")
+        .create_or_get("title='hello'", record)
+        .await
+        .unwrap();
+
+    create.assert_hits(0);
+    assert!(matches!(outcome, Outcome::Existing(ref r) if r.id == "a1"));
+}
+
+#[tokio::test]
+async fn create_or_get_creates_missing_record() {
     let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "totalPages": -1,
+            "items": []
+        }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .json_body(json!({ "id": "", "title": "hello" }));
+        then.status(200)
+            .json_body(json!({ "id": "b2", "title": "hello" }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: String::new(),
+        title: "hello".to_string(),
+    };
+    let outcome = client
+        .records("posts")
+        .create_or_get("title='hello'", record)
+        .await
+        .unwrap();
+
+    create.assert();
+    assert_eq!(outcome.record().id, "b2");
+    assert!(matches!(outcome, Outcome::Created(_)));
+}
+
+#[tokio::test]
+async fn create_or_get_reports_undecodable_existing_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": -1,
+            "totalPages": -1,
+            "items": [{ "id": "a1", "title": 42 }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_public_writes();
+    let record = Record {
+        id: String::new(),
+        title: "hello".to_string(),
+    };
+    let err = client
+        .records("posts")
+        .create_or_get("title='hello'", record)
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordWriteError::Decode { body_snippet, .. } => {
+            assert!(body_snippet.contains(r#""title":42"#), "{}", body_snippet)
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn view_record_with_flattened_base_model() {
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    struct Post {
+        #[serde(flatten)]
+        base: BaseModel,
+        title: String,
+    }
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200).json_body(json!({
+            "id": "a1",
+            "collectionId": "pbc_1125843985",
+            "collectionName": "posts",
+            "created": "2022-06-25 11:03:35.163Z",
+            "updated": "2022-06-26 09:12:01.120Z",
+            "title": "hello"
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let post: Post = client.records("posts").view("a1").call().await.unwrap();
+
+    assert_eq!(post.base.id, "a1");
+    assert_eq!(post.base.collection_name, "posts");
+    assert_eq!(
+        post.base.updated,
+        "2022-06-26T09:12:01.120Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    assert!(post.base.created < post.base.updated);
+    assert_eq!(post.title, "hello");
+}
+
+#[tokio::test]
+async fn list_reports_total_pages_and_next_page() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 2,
+            "totalItems": 5,
+            "totalPages": 3,
+            "items": [{ "id": "c3", "title": "" }, { "id": "d4", "title": "" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "3");
+        then.status(200).json_body(json!({
+            "page": 3,
+            "perPage": 2,
+            "totalItems": 5,
+            "totalPages": 3,
+            "items": [{ "id": "e5", "title": "" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("skipTotal", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 2,
+            "totalItems": -1,
+            "totalPages": -1,
+            "items": [{ "id": "a1", "title": "" }, { "id": "b2", "title": "" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let list = client.records("posts").list().per_page(2);
+
+    let middle = list.page(2).call::<Record>().await.unwrap();
+    assert_eq!(middle.total_pages, 3);
+    assert!(middle.has_next_page());
+
+    let last = list.page(3).call::<Record>().await.unwrap();
+    assert!(!last.has_next_page());
+
+    let uncounted = list.skip_total(true).call::<Record>().await.unwrap();
+    assert_eq!(uncounted.total_pages, -1);
+    assert!(uncounted.has_next_page());
+}
+
+#[tokio::test]
+async fn count_returns_total_without_items() {
+    let server = MockServer::start();
+    let filtered = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "published = true")
+            .query_param("perPage", "1")
+            .query_param("fields", "id")
+            .matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(key, _)| key == "skipTotal")
+            });
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": 42,
+            "totalPages": 42,
+            "items": [{ "id": "a1" }]
+        }));
+    });
+    let all = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(key, _)| key == "filter")
+            });
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": 0,
+            "totalPages": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client.records("posts");
+
+    assert_eq!(posts.count(Some("published = true")).await.unwrap(), 42);
+    assert_eq!(posts.count(None).await.unwrap(), 0);
+    filtered.assert();
+    all.assert();
+}
+
+#[tokio::test]
+async fn list_ids_pages_through_ids_only() {
+    let server = MockServer::start();
+    let pages: Vec<_> = [
+        (1, json!([{ "id": "a1" }, { "id": "b2" }])),
+        (2, json!([{ "id": "c3" }])),
+    ]
+    .into_iter()
+    .map(|(page, items)| {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/collections/posts/records")
+                .query_param("filter", "published = true")
+                .query_param("fields", "id")
+                .query_param("page", page.to_string());
+            then.status(200).json_body(json!({
+                "page": page,
+                "perPage": 2,
+                "totalItems": 3,
+                "totalPages": 2,
+                "items": items
+            }));
+        })
+    })
+    .collect();
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(2);
+    let ids = client
+        .records("posts")
+        .list_ids(Some("published = true"))
+        .await
+        .unwrap();
+
+    assert_eq!(ids, vec!["a1", "b2", "c3"]);
+    for page in &pages {
+        page.assert();
+    }
+}
+
+#[tokio::test]
+async fn list_records_with_typed_expand() {
+    #[derive(Clone, Debug, Default, Deserialize)]
+    struct Post {
+        id: String,
+        title: String,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    struct Tag {
+        name: String,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    struct PostExpand {
+        author: Option<Record>,
+        #[serde(default)]
+        tags: Vec<Tag>,
+    }
+
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("expand", "author,tags");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 30,
+            "totalItems": 2,
+            "totalPages": 1,
+            "items": [
+                {
+                    "id": "p1",
+                    "title": "hello",
+                    "author": "u1",
+                    "tags": ["t1", "t2"],
+                    "expand": {
+                        "author": { "id": "u1", "title": "ana" },
+                        "tags": [{ "name": "rust" }, { "name": "db" }]
+                    }
+                },
+                { "id": "p2", "title": "draft", "author": "", "tags": [] }
+            ]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client
+        .records("posts")
+        .list()
+        .expand("author,tags")
+        .call::<Expanded<Post, PostExpand>>()
+        .await
+        .unwrap();
+    list.assert();
+
+    let first = &posts.items[0];
+    assert_eq!(first.record.id, "p1");
+    let expand = first.expand.as_ref().unwrap();
+    assert_eq!(expand.author.as_ref().unwrap().title, "ana");
+    let tags: Vec<_> = expand.tags.iter().map(|tag| tag.name.as_str()).collect();
+    assert_eq!(tags, ["rust", "db"]);
+
+    assert_eq!(posts.items[1].record.title, "draft");
+    assert!(posts.items[1].expand.is_none());
+}
+
+#[tokio::test]
+async fn view_record_with_expanded_relation() {
+    #[derive(Clone, Debug, Default, Deserialize)]
+    struct PostExpand {
+        author: Option<Record>,
+    }
+
+    let server = MockServer::start();
+    let view = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records/p1")
+            .query_param("expand", "author")
+            .query_param("fields", "id,title,expand.author.id,expand.author.title");
+        then.status(200).json_body(json!({
+            "id": "p1",
+            "title": "hello",
+            "expand": { "author": { "id": "u1", "title": "ana" } }
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let post = client
+        .records("posts")
+        .view("p1")
+        .expand("author")
+        .fields("id,title,expand.author.id,expand.author.title")
+        .call::<Expanded<Record, PostExpand>>()
+        .await
+        .unwrap();
+
+    view.assert();
+    assert_eq!(post.record.title, "hello");
+    assert_eq!(post.expand.unwrap().author.unwrap().id, "u1");
+}
+
+#[tokio::test]
+async fn update_round_trip_omits_write_only_password() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let mut user = client
+        .records("users")
+        .view("8171022dc95a4ed")
+        .call::<User>()
+        .await
+        .unwrap();
+    assert!(user.password.is_unset());
+
+    user.email = "changed@example.com".to_string();
+    let updated = client
+        .records("users")
+        .update("8171022dc95a4ed", user)
+        .call()
+        .await;
+    assert!(updated.is_ok());
+}
+
+#[tokio::test]
+async fn view_and_list_records_as_dynamic_maps() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let user = client
+        .records("users")
+        .view_dynamic("8171022dc95a4ed")
+        .await
+        .unwrap();
+    assert_eq!(user["email"], "test@example.com");
+    assert_eq!(user["collectionName"], "users");
+    assert!(!user.contains_key("password"));
+
+    let comments = client.records("comments").list_dynamic().await.unwrap();
+    assert_eq!(comments.items.len(), 3);
+    assert_eq!(comments.items[1]["title"], 42);
+}
+
+#[tokio::test]
+async fn records_accepts_runtime_collection_names() {
+    let server = MockServer::start();
+    let views: Vec<_> = ["posts_2023", "posts_2024"]
+        .iter()
+        .map(|name| {
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path(format!("/api/collections/{}/records/a1", name));
+                then.status(200).json_body(json!({ "id": "a1" }));
+            })
+        })
+        .collect();
+
+    let client = Client::new(server.base_url().as_str());
+    for year in [2023, 2024] {
+        let name = format!("posts_{}", year);
+        let record = client
+            .records(&name)
+            .view("a1")
+            .call::<serde_json::Value>()
+            .await
+            .unwrap();
+        assert_eq!(record["id"], "a1");
+    }
+    for view in &views {
+        view.assert();
+    }
+}
+
+#[tokio::test]
+async fn get_all_lenient_collects_item_errors() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let (records, errors) = client
+        .records("comments")
+        .list()
+        .get_all_lenient::<Record>()
+        .await
+        .unwrap();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].index, 1);
+    assert_eq!(errors[0].id.as_deref(), Some("b2"));
+}
+
+#[tokio::test]
+async fn list_without_total_items_pages_until_short_page() {
+    let server = MockServer::start();
+    let first_page: Vec<_> = (0..1000)
+        .map(|i| json!({ "id": format!("p{}", i), "title": "post" }))
+        .collect();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1000,
+            "items": first_page
+        }));
+    });
+    let second_page = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 1000,
+            "items": [{ "id": "p1000", "title": "post" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let first = client
+        .records("posts")
+        .list()
+        .call::<Record>()
+        .await
+        .unwrap();
+    assert_eq!(first.total_items, -1);
+
+    let all = client
+        .records("posts")
+        .list()
+        .get_all::<Record>()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 1001);
+    second_page.assert_hits(1);
+}
+
+#[tokio::test]
+async fn per_page_is_clamped_to_max_per_page() {
+    let server = MockServer::start();
+    let clamped = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("perPage", "500");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 500,
+            "totalItems": 1,
+            "items": [{ "id": "a1", "title": "post" }]
+        }));
+    });
+    let custom = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("perPage", "200");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 200,
+            "totalItems": 1,
+            "items": [{ "id": "a1", "title": "post" }]
+        }));
+    });
+
+    let smallest = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("perPage", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1,
+            "totalItems": 1,
+            "items": [{ "id": "a1", "title": "post" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client.records("posts");
+    let streamed: Vec<Record> = posts
+        .list()
+        .per_page(1000)
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(streamed.len(), 1);
+    posts.list().get_all::<Record>().await.unwrap();
+    posts.get_all::<Record>().await.unwrap();
+    clamped.assert_hits(3);
+
+    let client = client.with_max_per_page(200);
+    let posts = client.records("posts");
+    let streamed: Vec<Record> = posts
+        .list()
+        .per_page(1000)
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(streamed.len(), 1);
+    posts.list().get_all::<Record>().await.unwrap();
+    custom.assert_hits(2);
+
+    let mut client = client;
+    client.max_per_page = 0;
+    let posts = client.records("posts");
+    posts.list().get_all::<Record>().await.unwrap();
+    posts.get_all::<Record>().await.unwrap();
+    smallest.assert_hits(2);
+}
+
+#[tokio::test]
+async fn get_all_with_progress_reports_each_page() {
+    let server = MockServer::start();
+    for (page, ids) in [("1", ["a1", "a2"].as_slice()), ("2", ["a3"].as_slice())] {
+        let items: Vec<_> = ids
+            .iter()
+            .map(|id| json!({ "id": id, "title": "post" }))
+            .collect();
+        server.mock(move |when, then| {
+            when.method(GET)
+                .path("/api/collections/posts/records")
+                .query_param("page", page);
+            then.status(200).json_body(json!({
+                "page": page.parse::<i32>().unwrap(),
+                "perPage": 2,
+                "totalItems": 3,
+                "items": items
+            }));
+        });
+    }
+
+    let client = Client::new(server.base_url().as_str()).with_max_per_page(2);
+    let mut reports = Vec::new();
+    let posts = client
+        .records("posts")
+        .list()
+        .get_all_with_progress::<Record>(|fetched, total| reports.push((fetched, total)))
+        .await
+        .unwrap();
+
+    assert_eq!(posts.len(), 3);
+    assert_eq!(reports, [(2, 3), (3, 3)]);
+}
+
+#[tokio::test]
+async fn get_all_stops_on_short_page_when_total_grows() {
+    let server = MockServer::start();
+    let first_page: Vec<_> = (0..1000)
+        .map(|i| json!({ "id": format!("p{}", i), "title": "post" }))
+        .collect();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1000,
+            "totalItems": 1001,
+            "items": first_page
+        }));
+    });
+    // A record was added after the first page was read.
+    let second_page = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 1000,
+            "totalItems": 1002,
+            "items": [{ "id": "p1000", "title": "post" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let from_manager = client.records("posts").get_all::<Record>().await.unwrap();
+    let from_list = client
+        .records("posts")
+        .list()
+        .get_all::<Record>()
+        .await
+        .unwrap();
+
+    assert_eq!(from_manager.len(), 1001);
+    assert_eq!(from_list.len(), 1001);
+    second_page.assert_hits(2);
+}
+
+#[tokio::test]
+async fn get_all_stops_when_total_shrinks() {
+    let server = MockServer::start();
+    let full_page = |offset: usize| -> Vec<_> {
+        (offset..offset + 1000)
+            .map(|i| json!({ "id": format!("p{}", i), "title": "post" }))
+            .collect()
+    };
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1000,
+            "totalItems": 2001,
+            "items": full_page(0)
+        }));
+    });
+    // Records were deleted after the first page was read.
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "2");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 1000,
+            "totalItems": 1500,
+            "items": full_page(1000)
+        }));
+    });
+    let beyond = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "3");
+        then.status(200).json_body(json!({
+            "page": 3,
+            "perPage": 1000,
+            "totalItems": 1500,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let from_manager = client.records("posts").get_all::<Record>().await.unwrap();
+    let from_list = client
+        .records("posts")
+        .list()
+        .get_all::<Record>()
+        .await
+        .unwrap();
+
+    assert_eq!(from_manager.len(), 2000);
+    assert_eq!(from_list.len(), 2000);
+    beyond.assert_hits(0);
+}
+
+#[tokio::test]
+async fn get_all_with_skip_total_stops_on_short_page() {
+    let server = MockServer::start();
+    let full_page: Vec<_> = (0..1000)
+        .map(|i| json!({ "id": format!("p{}", i), "title": "post" }))
+        .collect();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "1")
+            .query_param("skipTotal", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1000,
+            "totalItems": -1,
+            "items": full_page
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("page", "2")
+            .query_param("skipTotal", "1");
+        then.status(200).json_body(json!({
+            "page": 2,
+            "perPage": 1000,
+            "totalItems": -1,
+            "items": [{ "id": "p1000", "title": "post" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let records = client
+        .records("posts")
+        .list()
+        .skip_total(true)
+        .get_all::<Record>()
+        .await
+        .unwrap();
+
+    assert_eq!(records.len(), 1001);
+}
+
+#[tokio::test]
+async fn get_all_appends_id_tiebreaker_to_sort() {
+    let server = MockServer::start();
+    let mock_sorted = |sort: &str| {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/collections/posts/records")
+                .query_param("sort", sort);
+            then.status(200).json_body(json!({
+                "page": 1,
+                "perPage": 1000,
+                "totalItems": 1,
+                "items": [{ "id": "a1", "title": "post" }]
+            }));
+        })
+    };
+    let by_id = mock_sorted("id");
+    let by_created = mock_sorted("-created,id");
+    let by_id_desc = mock_sorted("-created,-id");
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client.records("posts");
+    posts.get_all::<Record>().await.unwrap();
+    posts.list().get_all::<Record>().await.unwrap();
+    posts
+        .list()
+        .sort("-created")
+        .get_all::<Record>()
+        .await
+        .unwrap();
+    posts
+        .list()
+        .sort("-created,-id")
+        .get_all::<Record>()
+        .await
+        .unwrap();
+
+    by_id.assert_hits(2);
+    by_created.assert_hits(1);
+    by_id_desc.assert_hits(1);
+}
+
+#[tokio::test]
+async fn get_all_respects_max_pages() {
+    let server = MockServer::start();
+    let full_page: Vec<_> = (0..1000)
+        .map(|i| json!({ "id": format!("p{}", i), "title": "post" }))
+        .collect();
+    // A misbehaving server that never runs out of full pages.
+    let pages = server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 1000,
+            "totalItems": 1_000_000_000,
+            "items": full_page
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .max_pages(3)
+        .get_all::<Record>()
+        .await
+        .unwrap_err();
+
+    let limit = err.downcast_ref::<PageLimitError>().unwrap();
+    assert_eq!(limit.max_pages, 3);
+    assert_eq!(limit.fetched, 3000);
+    pages.assert_hits(3);
+}
+
+#[tokio::test]
+async fn stream_ends_with_page_limit_error() {
+    let server = MockServer::start();
+    let pages = server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 2,
+            "totalItems": 100,
+            "items": [{ "id": "a", "title": "post" }, { "id": "b", "title": "post" }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let items: Vec<_> = client
+        .records("posts")
+        .list()
+        .per_page(2)
+        .max_pages(2)
+        .stream::<Record>()
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 5);
+    assert!(items[..4].iter().all(Result::is_ok));
+    let limit = items[4].as_ref().unwrap_err();
+    assert_eq!(limit.downcast_ref::<PageLimitError>().unwrap().fetched, 4);
+    pages.assert_hits(2);
+}
+
+#[tokio::test]
+async fn stream_fetches_pages_lazily() {
+    let server = MockServer::start();
+    let pages: Vec<_> = [vec!["a", "b"], vec!["c", "d"], vec!["e"]]
+        .iter()
+        .enumerate()
+        .map(|(i, ids)| {
+            let page = (i + 1).to_string();
+            let items: Vec<_> = ids
+                .iter()
+                .map(|id| json!({ "id": id, "title": "post" }))
+                .collect();
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path("/api/collections/posts/records")
+                    .query_param("page", &page)
+                    .query_param("perPage", "2")
+                    .query_param("filter", "title='post'");
+                then.status(200).json_body(json!({
+                    "page": i + 1,
+                    "perPage": 2,
+                    "totalItems": 5,
+                    "items": items
+                }));
+            })
+        })
+        .collect();
+
+    let client = Client::new(server.base_url().as_str());
+    let list = client
+        .records("posts")
+        .list()
+        .filter("title='post'")
+        .per_page(2);
+    let mut records = Box::pin(list.stream::<Record>());
+
+    let first: Vec<Record> = records.by_ref().take(2).try_collect().await.unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(pages[0].hits(), 1);
+    assert_eq!(pages[1].hits(), 0);
+
+    let rest: Vec<Record> = records.try_collect().await.unwrap();
+    let ids: Vec<_> = rest.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["c", "d", "e"]);
+    for page in &pages {
+        page.assert_hits(1);
+    }
+}
+
+#[tokio::test]
+async fn fields_limits_returned_columns() {
+    #[derive(Debug, Default, Deserialize)]
+    struct PostSummary {
+        id: String,
+        title: Option<String>,
+        #[serde(default)]
+        views: i64,
+    }
+
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("fields", "id,title");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 1,
+            "items": [{ "id": "a1", "title": "hello" }]
+        }));
+    });
+    let view_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records/a1")
+            .query_param("fields", "id");
+        then.status(200).json_body(json!({ "id": "a1" }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let list = client
+        .records("posts")
+        .list()
+        .fields("id,title")
+        .call::<PostSummary>()
+        .await
+        .unwrap();
+    assert_eq!(list.items[0].title.as_deref(), Some("hello"));
+    assert_eq!(list.items[0].views, 0);
+
+    let post = client
+        .records("posts")
+        .view("a1")
+        .fields("id")
+        .call::<PostSummary>()
+        .await
+        .unwrap();
+    assert_eq!(post.id, "a1");
+    assert!(post.title.is_none());
+
+    list_mock.assert();
+    view_mock.assert();
+}
+
+#[tokio::test]
+async fn saved_query_params_reproduce_the_request() {
+    let server = MockServer::start();
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "views > 10")
+            .query_param("sort", "-created")
+            .query_param("expand", "author");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let original = client
+        .records("posts")
+        .list()
+        .filter("views > 10")
+        .sort("-created")
+        .expand("author");
+    let saved = serde_json::to_string(&original.params()).unwrap();
+    assert_eq!(
+        saved,
+        r#"{"filter":"views > 10","sort":"-created","expand":"author"}"#
+    );
+
+    let restored: QueryParams = serde_json::from_str(&saved).unwrap();
+    let reapplied = client.records("posts").list().with_params(restored);
+    assert_eq!(reapplied.params(), original.params());
+
+    original.call::<Record>().await.unwrap();
+    reapplied.call::<Record>().await.unwrap();
+    list_mock.assert_hits(2);
+}
+
+#[tokio::test]
+async fn list_sends_extra_query_params() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", "published = true")
+            .query_param("page", "1")
+            .query_param("tag", "rust")
+            .query_param("preview", "1");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 0,
+            "totalPages": 0,
+            "items": []
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    client
+        .records("posts")
+        .list()
+        .filter("published = true")
+        .query_param("tag", "rust")
+        .query_param("preview", "1")
+        .call::<serde_json::Value>()
+        .await
+        .unwrap();
+    list.assert();
+}
+
+#[tokio::test]
+async fn records_call_with_meta_exposes_headers() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200)
+            .header("X-RateLimit-Remaining", "9")
+            .json_body(json!({
+                "page": 1,
+                "perPage": 100,
+                "totalItems": 1,
+                "items": [{ "id": "a1", "title": "hello" }]
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200)
+            .header("Cache-Control", "no-cache")
+            .json_body(json!({ "id": "a1", "title": "hello" }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let (meta, list) = client
+        .records("posts")
+        .list()
+        .call_with_meta::<Record>()
+        .await
+        .unwrap();
+    assert_eq!(meta.status, 200);
+    assert_eq!(meta.header("x-ratelimit-remaining"), Some("9"));
+    assert_eq!(list.items[0].id, "a1");
+
+    let (meta, record) = client
+        .records("posts")
+        .view("a1")
+        .call_with_meta::<Record>()
+        .await
+        .unwrap();
+    assert_eq!(meta.header("cache-control"), Some("no-cache"));
+    assert_eq!(record.title, "hello");
+}
+
+#[tokio::test]
+async fn call_streaming_decodes_multi_megabyte_list() {
+    let items: Vec<_> = (0..20_000)
+        .map(|i| json!({ "id": format!("r{}", i), "title": "x".repeat(200) }))
+        .collect();
+    let body = json!({
+        "page": 1,
+        "perPage": 20_000,
+        "totalItems": 20_000,
+        "items": items
+    });
+    assert!(body.to_string().len() > 4_000_000);
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(body);
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let list = client.records("posts").list().per_page(20_000);
+    let streamed = list.call_streaming::<Record>().await.unwrap();
+    let buffered = list.call::<Record>().await.unwrap();
+
+    assert_eq!(streamed.total_items, 20_000);
+    assert_eq!(streamed.items.len(), buffered.items.len());
+    assert_eq!(streamed.items[19_999].id, "r19999");
+    assert_eq!(streamed.items[19_999].title, buffered.items[19_999].title);
+}
+
+#[tokio::test]
+async fn list_call_reports_typed_decode_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 2,
+            "items": [{ "id": "a1", "title": "ok" }, { "id": "b2", "title": 42 }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .call::<Record>()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordListError::Decode {
+            path, body_snippet, ..
+        } => {
+            assert_eq!(path, "items[1].title");
+            assert!(body_snippet.contains(r#""title":42"#), "{}", body_snippet);
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn list_call_reports_http_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(400).json_body(json!({
+            "code": 400,
+            "message": "Something went wrong while processing your request. Invalid filter parameters.",
+            "data": {}
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .filter("title ~~")
+        .call::<Record>()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&err, RecordListError::Http { status: 400, body_snippet, .. } if body_snippet.contains("Invalid filter")),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn list_call_truncates_non_ascii_error_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .call::<Record>()
+        .await
+        .unwrap_err();
+
+    match err {
+        RecordListError::Http { body_snippet, .. } => {
+            assert_eq!(body_snippet, "€".repeat(666))
+        }
+        other => panic!("expected an HTTP error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn call_streaming_reports_decode_path_and_snippet() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 1,
+            "items": [{ "id": "a1", "title": 42 }]
+        }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .call_streaming::<Record>()
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("items[0].title"), "{}", err);
+    assert!(err.contains(r#"{"id":"a1","title":42}"#), "{}", err);
+}
+
+#[tokio::test]
+async fn call_streaming_truncates_non_ascii_error_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .list()
+        .call_streaming::<Record>()
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.ends_with(&format!("\n{}", "€".repeat(666))), "{}", err);
+}
+
+#[tokio::test]
+async fn view_call_streaming_decodes_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200)
+            .json_body(json!({ "id": "a1", "body": "x".repeat(100_000) }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let record: serde_json::Value = client
+        .records("posts")
+        .view("a1")
+        .call_streaming()
+        .await
+        .unwrap();
+    assert_eq!(record["body"].as_str().unwrap().len(), 100_000);
+}
+
+#[tokio::test]
+async fn view_call_streaming_truncates_non_ascii_error_body() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(500).body("€".repeat(1000));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .records("posts")
+        .view("a1")
+        .call_streaming::<serde_json::Value>()
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.ends_with(&format!("\n{}", "€".repeat(666))), "{}", err);
+}
+
+#[tokio::test]
+async fn max_body_size_rejects_large_responses() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records");
+        then.status(200).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 1,
+            "totalPages": 1,
+            "items": [{ "id": "a1", "body": "x".repeat(2000) }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/a1");
+        then.status(200)
+            .json_body(json!({ "id": "a1", "body": "x".repeat(2000) }));
+    });
+
+    let client = Client::new(server.base_url().as_str()).with_max_body_size(1024);
+    let posts = client.records("posts");
+
+    let RecordListError::Transport(err) =
+        posts.list().call::<serde_json::Value>().await.unwrap_err()
+    else {
+        panic!("expected a transport error");
+    };
+    let too_large = err.downcast_ref::<BodyTooLargeError>().unwrap();
+    assert_eq!(too_large.limit, 1024);
+
+    let RecordViewError::Transport(err) = posts
+        .view("a1")
+        .call::<serde_json::Value>()
+        .await
+        .unwrap_err()
+    else {
+        panic!("expected a transport error");
+    };
+    assert!(err.is::<BodyTooLargeError>());
+
+    let err = posts
+        .view("a1")
+        .call_streaming::<serde_json::Value>()
+        .await
+        .unwrap_err();
+    assert!(err.is::<BodyTooLargeError>());
+
+    let roomy = Client::new(server.base_url().as_str()).with_max_body_size(4096);
+    let record = roomy
+        .records("posts")
+        .view("a1")
+        .call::<serde_json::Value>()
+        .await
+        .unwrap();
+    assert_eq!(record["id"], "a1");
+}
+
+#[tokio::test]
+async fn list_records_skips_empty_expand() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let without_expand = client
+        .records("tags")
+        .list()
+        .expand_opt(None)
+        .call::<Record>()
+        .await;
+    assert!(without_expand.is_ok());
+
+    let empty_expand = client
+        .records("tags")
+        .list()
+        .expand("")
+        .call::<Record>()
+        .await;
+    assert!(empty_expand.is_ok());
+}
+
+#[tokio::test]
+async fn list_records_send_raw_exposes_response() {
+    let mockserver = mock_records_server();
+    let client = Client::new(mockserver.base_url().as_str())
+        .auth_with_password("users", "", "")
+        .await
+        .unwrap();
+
+    let response = client.records("tags").list().send_raw().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-request-id"], "abc123");
+}
+
+#[tokio::test]
+async fn transport_errors_name_the_failed_request() {
+    let client = Client::new("http://127.0.0.1:1").with_public_writes();
+
+    let RecordListError::Transport(err) = client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await
+        .unwrap_err()
+    else {
+        panic!("expected a transport error");
+    };
+    let message = format!("{:#}", err);
+    assert!(
+        message.starts_with("GET http://127.0.0.1:1/api/collections/posts/records?"),
+        "{}",
+        message
+    );
+    assert!(err.downcast_ref::<reqwest::Error>().is_some());
+
+    let err = client
+        .records("posts")
+        .destroy("a1")
+        .call()
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("DELETE http://127.0.0.1:1/api/collections/posts/records/a1"),
+        "{:#}",
+        err
+    );
+}
+
+fn mock_records_server() -> MockServer {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/tags/records")
+            .matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(key, _)| key == "expand")
+            });
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .header("X-Request-Id", "abc123")
+            .json_body(json!({
+                "page": 1,
+                "perPage": 100,
+                "totalItems": 0,
+                "items": []
+            }));
+    });
+
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/comments/records");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .json_body(json!({
+                "page": 1,
+                "perPage": 1000,
+                "totalItems": 3,
+                "items": [
+                    { "id": "a1", "title": "first" },
+                    { "id": "b2", "title": 42 },
+                    { "id": "c3", "title": "third" }
+                ]
+            }));
+    });
+
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/users/records/8171022dc95a4ed");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .json_body(json!({
+                "id": "8171022dc95a4ed",
+                "collectionId": "d2972397d45614e",
+                "collectionName": "users",
+                "created": "2022-06-24 06:24:18.434Z",
+                "updated": "2022-06-24 06:24:18.889Z",
+                "email": "test@example.com"
+            }));
+    });
+
+    server.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/api/collections/users/records/8171022dc95a4ed")
+            .json_body(json!({
+                "id": "8171022dc95a4ed",
+                "email": "changed@example.com"
+            }));
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .json_body(json!({
+                "id": "8171022dc95a4ed",
+                "created": "2022-06-24 06:24:18.434Z",
+                "updated": "2022-06-26 09:12:01.120Z",
+                "email": "changed@example.com"
+            }));
+    });
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/posts/records")
+            .body_contains("name=\"title\"")
+            .body_contains("filename=\"pocketbase-client-upload.txt\"")
+            .body_contains("Content-Type: text/plain")
+            .body_contains("uploaded file contents");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .json_body(json!({
+                "@collectionName": "posts",
+                "@collectionId": "a98f514eb05f454",
+                "id": "f1e2d3c4b5a6978",
+                "created": "2022-06-25 11:03:35.163Z",
+                "updated": "2022-06-25 11:03:35.163Z",
+                "title": "test3",
+                "document": "pocketbase_client_upload_a1b2c3.txt"
+            }));
+    });
     server.mock(|when, then| {
         when.method(GET)
             .path("/api/collections/posts/records")
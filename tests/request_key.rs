@@ -0,0 +1,139 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::error::{RecordListError, RequestAbortedError};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+fn mock_search(server: &MockServer, filter: &str, delay: Duration) {
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("filter", filter);
+        then.status(200).delay(delay).json_body(json!({
+            "page": 1,
+            "perPage": 100,
+            "totalItems": 1,
+            "totalPages": 1,
+            "items": [{ "title": filter }]
+        }));
+    });
+}
+
+#[tokio::test]
+async fn newer_request_with_same_key_aborts_older_one() {
+    let server = MockServer::start();
+    mock_search(&server, "a", Duration::from_secs(10));
+    mock_search(&server, "ab", Duration::ZERO);
+
+    let client = Client::new(server.base_url().as_str());
+    let records = client.records("posts");
+    let older = records.list().filter("a").request_key("search");
+    let newer = records.list().filter("ab").request_key("search");
+
+    let (older, newer) = tokio::join!(older.call::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        newer.call::<Value>().await
+    });
+
+    match older {
+        Err(RecordListError::Transport(err)) => {
+            let aborted = err.downcast_ref::<RequestAbortedError>().unwrap();
+            assert_eq!(aborted.request_key, "search");
+        }
+        other => panic!("expected an aborted request, got {:?}", other),
+    }
+    assert_eq!(newer.unwrap().items[0]["title"], "ab");
+}
+
+#[tokio::test]
+async fn unkeyed_and_differently_keyed_requests_all_complete() {
+    let server = MockServer::start();
+    mock_search(&server, "a", Duration::from_secs(1));
+    mock_search(&server, "ab", Duration::ZERO);
+
+    let client = Client::new(server.base_url().as_str());
+    let records = client.records("posts");
+
+    let first = records.list().filter("a");
+    let second = records.list().filter("ab");
+    let (first_result, second_result) = tokio::join!(first.call::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        second.call::<Value>().await
+    });
+    assert!(first_result.is_ok() && second_result.is_ok());
+
+    let first = first.request_key("left");
+    let second = second.request_key("right");
+    let (first_result, second_result) = tokio::join!(first.call::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        second.call::<Value>().await
+    });
+    assert!(first_result.is_ok() && second_result.is_ok());
+}
+
+#[tokio::test]
+async fn view_requests_share_keys_across_client_clones() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/slow");
+        then.status(200)
+            .delay(Duration::from_secs(10))
+            .json_body(json!({ "id": "slow" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/fast");
+        then.status(200).json_body(json!({ "id": "fast" }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let other = client.clone();
+    let client_records = client.records("posts");
+    let other_records = other.records("posts");
+    let slow = client_records.view("slow").request_key("detail");
+    let fast = other_records.view("fast").request_key("detail");
+
+    let (slow, fast) = tokio::join!(slow.call::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        fast.call::<Value>().await
+    });
+
+    assert!(slow.unwrap_err().to_string().contains("detail"));
+    assert_eq!(fast.unwrap()["id"], "fast");
+}
+
+#[tokio::test]
+async fn streaming_calls_honour_request_keys() {
+    let server = MockServer::start();
+    mock_search(&server, "a", Duration::from_secs(10));
+    mock_search(&server, "ab", Duration::ZERO);
+    server.mock(|when, then| {
+        when.method(GET).path("/api/collections/posts/records/slow");
+        then.status(200)
+            .delay(Duration::from_secs(10))
+            .json_body(json!({ "id": "slow" }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let records = client.records("posts");
+    let older = records.list().filter("a").request_key("search");
+    let newer = records.list().filter("ab").request_key("search");
+    let (older, newer) = tokio::join!(older.call_streaming::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        newer.call::<Value>().await
+    });
+
+    let aborted = older.unwrap_err();
+    assert!(aborted.downcast_ref::<RequestAbortedError>().is_some());
+    assert_eq!(newer.unwrap().items[0]["title"], "ab");
+
+    let slow = records.view("slow").request_key("detail");
+    let newer = records.list().filter("ab").request_key("detail");
+    let (slow, newer) = tokio::join!(slow.call_streaming::<Value>(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        newer.call::<Value>().await
+    });
+
+    let aborted = slow.unwrap_err();
+    assert!(aborted.downcast_ref::<RequestAbortedError>().is_some());
+    assert!(newer.is_ok());
+}
@@ -0,0 +1,170 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::retry::RetryPolicy;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new(3).base_delay(Duration::from_millis(10))
+}
+
+fn mock_unavailable(server: &MockServer) -> httpmock::Mock<'_> {
+    server.mock(|when, then| {
+        when.path("/api/collections/posts/records");
+        then.status(503)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 503,
+                "message": "Service unavailable.",
+                "data": {}
+            }));
+    })
+}
+
+#[tokio::test]
+async fn client_policy_retries_get() {
+    let server = MockServer::start();
+    let mock = mock_unavailable(&server);
+    let client = Client::new(server.base_url().as_str()).with_retry_policy(fast_policy());
+
+    let records = client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 3);
+}
+
+#[tokio::test]
+async fn no_retry_overrides_client_policy() {
+    let server = MockServer::start();
+    let mock = mock_unavailable(&server);
+    let client = Client::new(server.base_url().as_str()).with_retry_policy(fast_policy());
+
+    let records = client
+        .records("posts")
+        .list()
+        .no_retry()
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 1);
+}
+
+#[tokio::test]
+async fn request_retry_applies_without_client_policy() {
+    let server = MockServer::start();
+    let mock = mock_unavailable(&server);
+    let client = Client::new(server.base_url().as_str());
+
+    let records = client
+        .records("posts")
+        .list()
+        .retry(fast_policy())
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 3);
+}
+
+#[tokio::test]
+async fn post_is_not_retried_by_client_policy() {
+    let server = MockServer::start();
+    let mock = mock_unavailable(&server);
+    let client = Client::new(server.base_url().as_str())
+        .with_public_writes()
+        .with_retry_policy(fast_policy());
+
+    let created = client
+        .records("posts")
+        .create(json!({ "title": "hello" }))
+        .call()
+        .await;
+
+    assert!(created.is_err());
+    assert_eq!(mock.hits(), 1);
+}
+
+#[tokio::test]
+async fn retries_back_off_exponentially() {
+    let server = MockServer::start();
+    let mock = mock_unavailable(&server);
+    let policy = RetryPolicy::new(4)
+        .base_delay(Duration::from_millis(50))
+        .max_delay(Duration::from_millis(150));
+    let client = Client::new(server.base_url().as_str()).with_retry_policy(policy);
+
+    let started = Instant::now();
+    let records = client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 4);
+    // 50ms, then 100ms, then 200ms capped at 150ms.
+    assert!(started.elapsed() >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn rate_limited_retry_honors_retry_after() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.path("/api/collections/posts/records");
+        then.status(429)
+            .header("Retry-After", "1")
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 429,
+                "message": "Too Many Requests.",
+                "data": {}
+            }));
+    });
+    let client = Client::new(server.base_url().as_str())
+        .with_retry_policy(RetryPolicy::new(2).base_delay(Duration::from_millis(10)));
+
+    let started = Instant::now();
+    let records = client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 2);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_at_max_delay() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.path("/api/collections/posts/records");
+        then.status(429)
+            .header("Retry-After", "3600")
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 429,
+                "message": "Too Many Requests.",
+                "data": {}
+            }));
+    });
+    let policy = RetryPolicy::new(2).max_delay(Duration::from_millis(100));
+    let client = Client::new(server.base_url().as_str()).with_retry_policy(policy);
+
+    let started = Instant::now();
+    let records = client
+        .records("posts")
+        .list()
+        .call::<serde_json::Value>()
+        .await;
+
+    assert!(records.is_err());
+    assert_eq!(mock.hits(), 2);
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
@@ -0,0 +1,73 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use pocketbase_client::sort::Sort;
+use serde_json::{json, Value};
+
+#[test]
+fn sort_renders_direction_prefixes() {
+    let sort = Sort::desc("created")
+        .then(Sort::asc("title"))
+        .then(Sort::desc("id"));
+    assert_eq!(sort.to_string(), "-created,title,-id");
+    assert_eq!(Sort::random().to_string(), "@random");
+    assert_eq!(
+        String::from(Sort::random().then(Sort::asc("id"))),
+        "@random,id"
+    );
+}
+
+#[tokio::test]
+async fn list_builders_accept_sort_values_and_strings() {
+    let server = MockServer::start();
+    let empty = json!({
+        "page": 1,
+        "perPage": 100,
+        "totalItems": 0,
+        "totalPages": 0,
+        "items": []
+    });
+    let random = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("sort", "@random");
+        then.status(200).json_body(empty.clone());
+    });
+    let raw = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections/posts/records")
+            .query_param("sort", "-created,title");
+        then.status(200).json_body(empty.clone());
+    });
+    let collections = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/collections")
+            .query_param("sort", "-name");
+        then.status(200).json_body(empty.clone());
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let posts = client.records("posts");
+    posts
+        .list()
+        .sort(Sort::random())
+        .call::<Value>()
+        .await
+        .unwrap();
+    posts
+        .list()
+        .sort("-created,title")
+        .call::<Value>()
+        .await
+        .unwrap();
+    client
+        .collections()
+        .list()
+        .sort(Sort::desc("name"))
+        .call()
+        .await
+        .unwrap();
+
+    random.assert();
+    raw.assert();
+    collections.assert();
+}
@@ -0,0 +1,44 @@
+use pocketbase_client::client::Client;
+use pocketbase_client::error::{RecordListError, TimeoutError};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+/// Accepts connections but never answers them.
+async fn silent_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut sockets = Vec::new();
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            sockets.push(socket);
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn request_times_out_with_distinct_error() {
+    let url = silent_server().await;
+    let client = Client::new(&url).with_timeout(Duration::from_millis(200));
+
+    let started = Instant::now();
+    let err = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.records("posts").list().call::<Value>(),
+    )
+    .await
+    .expect("request should not hang past the client timeout")
+    .unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    let RecordListError::Transport(err) = err else {
+        panic!("expected a transport error, got {:?}", err);
+    };
+    let timeout = err
+        .downcast_ref::<TimeoutError>()
+        .unwrap_or_else(|| panic!("expected a timeout error, got {:?}", err));
+    assert_eq!(timeout.after, Duration::from_millis(200));
+    assert!(timeout.url.starts_with(&url));
+}
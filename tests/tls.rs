@@ -0,0 +1,24 @@
+#![cfg(any(feature = "native-tls", feature = "rustls"))]
+
+use httpmock::prelude::*;
+use pocketbase_client::client::Client;
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn accept_invalid_certs_keeps_client_settings() {
+    let server = MockServer::start();
+    let health = server.mock(|when, then| {
+        when.method(GET).path("/api/health");
+        then.status(200)
+            .json_body(json!({ "code": 200, "message": "API is healthy." }));
+    });
+
+    let client = Client::new(server.base_url().as_str())
+        .with_timeout(Duration::from_secs(5))
+        .danger_accept_invalid_certs(true);
+    assert_eq!(client.timeout, Some(Duration::from_secs(5)));
+
+    client.health_check().await.unwrap();
+    health.assert();
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "test-util")]
+
+use pocketbase_client::client::Client;
+use pocketbase_client::error::RecordViewError;
+use pocketbase_client::transport::MockTransport;
+use reqwest::Method;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn mock_transport_answers_matching_requests() {
+    let transport = MockTransport::new()
+        .respond(
+            Method::GET,
+            "/api/collections/posts/records/a1",
+            200,
+            json!({ "id": "a1", "title": "exact" }),
+        )
+        .respond(
+            Method::GET,
+            "/api/collections/posts/records*",
+            200,
+            json!({
+                "page": 1,
+                "perPage": 100,
+                "totalItems": 1,
+                "totalPages": 1,
+                "items": [{ "id": "b2", "title": "listed" }]
+            }),
+        );
+    let client = Client::new("http://pocketbase.invalid").with_transport(transport.clone());
+    let posts = client.records("posts");
+
+    let record = posts.view("a1").call::<Value>().await.unwrap();
+    let list = posts.list().call::<Value>().await.unwrap();
+
+    assert_eq!(record["title"], "exact");
+    assert_eq!(list.items[0]["title"], "listed");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].0, Method::GET);
+    assert!(requests[1]
+        .1
+        .starts_with("http://pocketbase.invalid/api/collections/posts/records?"));
+}
+
+#[tokio::test]
+async fn mock_transport_answers_unmatched_requests_with_not_found() {
+    let transport = MockTransport::new();
+    let client = Client::new("http://pocketbase.invalid").with_transport(transport);
+
+    let result = client
+        .records("posts")
+        .view("missing")
+        .call::<Value>()
+        .await;
+
+    assert!(matches!(result, Err(RecordViewError::NotFound { .. })));
+}
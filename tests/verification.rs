@@ -0,0 +1,100 @@
+use httpmock::prelude::*;
+use pocketbase_client::client::{AuthError, Client};
+use serde_json::json;
+
+#[tokio::test]
+async fn request_verification_success() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/request-verification")
+            .json_body(json!({ "email": "test@example.com" }));
+        then.status(204);
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    client
+        .request_verification("users", "test@example.com")
+        .await
+        .unwrap();
+    mock.assert();
+}
+
+#[tokio::test]
+async fn confirm_verification_validation_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/confirm-verification")
+            .json_body(json!({ "token": "expired" }));
+        then.status(400)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "code": 400,
+                "message": "Failed to verify.",
+                "data": {
+                    "token": {
+                        "code": "validation_invalid_token",
+                        "message": "Invalid or expired token."
+                    }
+                }
+            }));
+    });
+
+    let client = Client::new(server.base_url().as_str());
+    let err = client
+        .confirm_verification("users", "expired")
+        .await
+        .unwrap_err();
+    match err {
+        AuthError::Validation(body) => {
+            assert_eq!(body.data["token"].code, "validation_invalid_token")
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn confirm_own_verification_refreshes_record() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-with-password");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "token": "login-token",
+                "record": { "id": "8171022dc95a4ed", "verified": false }
+            }));
+    });
+    let confirm = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/confirm-verification")
+            .json_body(json!({ "token": "verification-token" }));
+        then.status(204);
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/collections/users/auth-refresh")
+            .header("Authorization", "login-token");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "token": "verified-token",
+                "record": { "id": "8171022dc95a4ed", "verified": true }
+            }));
+    });
+
+    let client = Client::new(server.base_url().as_str())
+        .auth_with_password("users", "test@example.com", "secret")
+        .await
+        .unwrap();
+    let refreshed = client
+        .confirm_verification_and_refresh("verification-token")
+        .await
+        .unwrap();
+
+    confirm.assert();
+    assert_eq!(refreshed.auth_token().as_deref(), Some("verified-token"));
+    assert_eq!(refreshed.state.record["verified"], true);
+}
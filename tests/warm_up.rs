@@ -0,0 +1,51 @@
+use pocketbase_client::client::Client;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A keep-alive HTTP server answering every request with a health check,
+/// counting the connections it accepts.
+async fn health_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = Arc::clone(&connections);
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let body = r#"{"code":200,"message":"API is healthy."}"#;
+                let mut buffer = [0u8; 4096];
+                while let Ok(read) = socket.read(&mut buffer).await {
+                    if read == 0 {
+                        break;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if socket.write_all(response.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (base_url, connections)
+}
+
+#[tokio::test]
+async fn warm_up_connection_is_reused() {
+    let (base_url, connections) = health_server().await;
+    let client = Client::new(&base_url);
+
+    client.warm_up().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    client.health_check().await.unwrap();
+    client.clone().health_check().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}